@@ -0,0 +1,86 @@
+//! Heap usage budgets of the solutions, checked by the `alloc_budget` test of every day.
+//!
+//! The test binary has to install `dhat::Alloc` as its `#[global_allocator]`.
+
+use std::fmt::Debug;
+
+/// Share of the measured usage, in percent, allowed on top of it.
+///
+/// Small changes in `std` or the dependencies move the measurements by a few blocks or
+/// bytes without the solution changing, so the budgets only fail beyond this headroom.
+/// A solution that does not allocate has no headroom at all.
+pub const HEADROOM_PERCENT: u64 = 10;
+
+/// The known answer of a part with its measured heap usage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget<T> {
+    pub answer: T,
+    /// Allocations over the whole run.
+    pub total_blocks: u64,
+    /// Bytes allocated at the peak.
+    pub max_bytes: usize,
+}
+
+impl<T> Budget<T> {
+    /// The most allocations the part may make.
+    pub fn blocks_limit(&self) -> u64 {
+        with_headroom(self.total_blocks)
+    }
+
+    /// The most bytes the part may hold at once.
+    pub fn bytes_limit(&self) -> usize {
+        // usize is at most 64 bits on every supported target
+        with_headroom(self.max_bytes as u64) as usize
+    }
+}
+
+fn with_headroom(measured: u64) -> u64 {
+    measured + (measured * HEADROOM_PERCENT).div_ceil(100)
+}
+
+/// Asserts that `process` finds the answer of the `part` on the `input` within the budget.
+///
+/// # Panics
+///
+/// If the answer is wrong or the heap usage is over the budget, or if a `dhat` profiler
+/// is already running.
+pub fn check<T: PartialEq + Debug>(
+    part: &str,
+    input: &str,
+    budget: Budget<T>,
+    process: fn(&str) -> T,
+) {
+    let _profiler = dhat::Profiler::builder().testing().build();
+    let answer = process(input);
+    let stats = dhat::HeapStats::get();
+
+    assert_eq!(budget.answer, answer, "{part}");
+    dhat::assert!(
+        stats.total_blocks <= budget.blocks_limit(),
+        "{part} made {} allocations, over the budget of {}",
+        stats.total_blocks,
+        budget.blocks_limit()
+    );
+    dhat::assert!(
+        stats.max_bytes <= budget.bytes_limit(),
+        "{part} peaked at {} bytes, over the budget of {}",
+        stats.max_bytes,
+        budget.bytes_limit()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, 0)]
+    #[case(3, 4)]
+    #[case(60, 66)]
+    #[case(1463, 1610)]
+    fn test_with_headroom(#[case] measured: u64, #[case] expected: u64) {
+        assert_eq!(expected, with_headroom(measured));
+    }
+}
@@ -0,0 +1,270 @@
+use std::ops::{Index, IndexMut};
+
+/// Position of a cell as `(x, y)`, with `x` growing to the right and `y` growing downwards.
+pub type Pos = (usize, usize);
+
+/// Offsets to the 4 orthogonal neighbours of a cell.
+pub const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets to the 8 orthogonal and diagonal neighbours of a cell, row by row.
+pub const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// Rectangular grid of cells stored row by row.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ParseGridError {
+    /// A row is not as wide as the first one. Rows are zero-based.
+    #[error("row {row} is {found} cells wide, expected {expected}")]
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl<T> Grid<T> {
+    /// # Panics
+    ///
+    /// If there are not exactly `width * height` cells.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(width * height, cells.len(), "cells do not fill the grid");
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Pos) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    /// Parses a grid with a cell per character of every line of the `text`.
+    pub fn parse_with(text: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        Self::parse_lines(text, |line, cells| cells.extend(line.chars().map(&mut f)))
+    }
+
+    fn parse_lines(
+        text: &str,
+        mut push_line: impl FnMut(&str, &mut Vec<T>),
+    ) -> Result<Self, ParseGridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (row, line) in text.lines().enumerate() {
+            let start = cells.len();
+            push_line(line, &mut cells);
+            let found = cells.len() - start;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseGridError::Ragged {
+                    row,
+                    expected,
+                    found,
+                });
+            }
+            height += 1;
+        }
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub const fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains((x, y))
+            .then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.contains((x, y))
+            .then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// The position `(dx, dy)` away from `(x, y)`, if it is inside the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Positions of the orthogonal neighbours of `pos` inside the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_4
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours of `pos` inside the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        OFFSETS_8
+            .into_iter()
+            .filter_map(move |offset| self.offset(pos, offset))
+    }
+
+    /// # Panics
+    ///
+    /// If `y` is outside the grid.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} is outside the grid");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Every row, empty ones included when the grid has no columns.
+    pub fn rows(&self) -> impl ExactSizeIterator<Item = &[T]> + DoubleEndedIterator + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    /// # Panics
+    ///
+    /// If `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl ExactSizeIterator<Item = &T> + DoubleEndedIterator + '_ {
+        assert!(x < self.width, "column {x} is outside the grid");
+        (0..self.height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    pub fn columns(&self) -> impl ExactSizeIterator<Item = impl Iterator<Item = &T>> + '_ {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Cells along with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid with a cell per byte of every line of the `text`.
+    pub fn parse_bytes(text: &str) -> Result<Self, ParseGridError> {
+        Self::parse_lines(text, |line, cells| cells.extend_from_slice(line.as_bytes()))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): Pos) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (x, y): Pos) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is outside the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const TEXT: &str = "abc
+def";
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse_bytes(TEXT).unwrap();
+        assert_eq!((3, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(&b'f'), grid.get(2, 1));
+        assert_eq!(None, grid.get(3, 0));
+        assert_eq!(b'd', grid[(0, 1)]);
+        assert_eq!(grid, Grid::parse_with(TEXT, |ch| ch as u8).unwrap());
+        assert_eq!(grid, Grid::parse_bytes("abc\r\ndef\r\n").unwrap());
+        assert_eq!(
+            Grid::new(0, 0, Vec::<u8>::new()),
+            Grid::parse_bytes("").unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("abc\nde", ParseGridError::Ragged { row: 1, expected: 3, found: 2 })]
+    #[case("ab\nab\nabc", ParseGridError::Ragged { row: 2, expected: 2, found: 3 })]
+    fn test_parse_ragged(#[case] text: &str, #[case] expected: ParseGridError) {
+        assert_eq!(Err(expected), Grid::parse_bytes(text));
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = Grid::parse_bytes(TEXT).unwrap();
+        assert_eq!(vec![b"abc", b"def"], grid.rows().collect::<Vec<_>>());
+        assert_eq!(b"def", grid.row(1));
+        assert_eq!(
+            vec![vec![b'a', b'd'], vec![b'b', b'e'], vec![b'c', b'f']],
+            grid.columns()
+                .map(|column| column.copied().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_rows_without_columns() {
+        let grid = Grid::parse_bytes("\n\n").unwrap();
+        assert_eq!((0, 2), (grid.width(), grid.height()));
+        assert_eq!(vec![b"", b""], grid.rows().collect::<Vec<_>>());
+        assert_eq!(b"", grid.row(1));
+        assert_eq!(1, grid.rows().rev().skip(1).count());
+    }
+
+    #[rstest]
+    #[case((0, 0), vec![(1, 0), (0, 1)], vec![(1, 0), (0, 1), (1, 1)])]
+    #[case((1, 0), vec![(0, 0), (2, 0), (1, 1)], vec![(0, 0), (2, 0), (0, 1), (1, 1), (2, 1)])]
+    #[case((2, 1), vec![(2, 0), (1, 1)], vec![(1, 0), (2, 0), (1, 1)])]
+    fn test_neighbours(#[case] pos: Pos, #[case] expected4: Vec<Pos>, #[case] expected8: Vec<Pos>) {
+        let grid = Grid::parse_bytes(TEXT).unwrap();
+        assert_eq!(expected4, grid.neighbours4(pos).collect::<Vec<_>>());
+        assert_eq!(expected8, grid.neighbours8(pos).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_offset() {
+        let grid = Grid::from_fn(3, 2, |(x, y)| x + y);
+        assert_eq!(Some((2, 1)), grid.offset((1, 0), (1, 1)));
+        assert_eq!(None, grid.offset((0, 0), (-1, 0)));
+        assert_eq!(None, grid.offset((2, 1), (1, 0)));
+        assert_eq!(3, grid[(2, 1)]);
+        assert_eq!(
+            vec![((0, 0), &0), ((1, 0), &1), ((2, 0), &2)],
+            grid.iter().take(3).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,3 @@
+#[cfg(feature = "dhat-heap")]
+pub mod alloc_budget;
+pub mod grid;
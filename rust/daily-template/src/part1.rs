@@ -1,15 +1,15 @@
-pub fn process(_input: &str) -> u32 {
-    todo!("{{project-name}} - part 1");
+pub fn process(_input: &str) -> u64 {
+    // solve {{project-name}} - part 1 here
+    0
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
 
-    #[test]
+    #[test]{{part1-ignore}}
     fn test_process() {
-        todo!("haven't built test yet");
-        // let input = "";
-        // assert_eq!(0, process(input));
+        let input = include_str!("../example.txt");
+        assert_eq!({{part1-answer}}, process(input));
     }
 }
@@ -1,15 +1,15 @@
-pub fn process(_input: &str) -> u32 {
-    todo!("{{project-name}} - part 2");
+pub fn process(_input: &str) -> u64 {
+    // solve {{project-name}} - part 2 here
+    0
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
 
-    #[test]
+    #[test]{{part2-ignore}}
     fn test_process() {
-        todo!("haven't built test yet");
-        // let input = "";
-        // assert_eq!(0, process(input));
+        let input = include_str!("../{{part2-example}}");
+        assert_eq!({{part2-answer}}, process(input));
     }
 }
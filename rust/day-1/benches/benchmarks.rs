@@ -15,3 +15,13 @@ fn part1() {
 fn part2() {
     part2::process(divan::black_box(INPUT));
 }
+
+#[divan::bench]
+fn part1_chunked() {
+    part1::process_chunked(divan::black_box(INPUT.as_bytes()), 4096).unwrap();
+}
+
+#[divan::bench]
+fn part2_chunked() {
+    part2::process_chunked(divan::black_box(INPUT.as_bytes()), 4096).unwrap();
+}
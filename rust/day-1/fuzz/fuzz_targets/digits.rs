@@ -0,0 +1,36 @@
+#![no_main]
+
+use day_1::part2::{reference_digits, Digits};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: (&str, Vec<bool>)| {
+    let (line, from_back) = input;
+
+    let expected = reference_digits(line);
+
+    let forward: Vec<u8> = Digits::new(line).collect();
+    let mut backward: Vec<u8> = Digits::new(line).rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+    assert_eq!(forward, expected);
+
+    let mut digits = Digits::new(line);
+    let mut front = Vec::new();
+    let mut back = Vec::new();
+    for from_back in from_back.into_iter().chain(std::iter::repeat(false)) {
+        let next = if from_back {
+            digits.next_back()
+        } else {
+            digits.next()
+        };
+        match (next, from_back) {
+            (Some(digit), false) => front.push(digit),
+            (Some(digit), true) => back.push(digit),
+            (None, _) => break,
+        }
+    }
+    assert_eq!(digits.next(), None);
+    assert_eq!(digits.next_back(), None);
+    front.extend(back.into_iter().rev());
+    assert_eq!(front, forward);
+});
@@ -1,14 +1,28 @@
+use std::io::{self, BufRead, Read};
+
+use crate::stream;
+
 pub fn process(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| {
-            let mut digits = line.chars().filter_map(char_to_digit);
-            let first = digits.next().expect("invalid input");
-            let last = digits.next_back().unwrap_or(first);
-
-            (first * 10 + last) as u32
-        })
-        .sum()
+    input.lines().map(process_line).sum()
+}
+
+/// Same as [`process`], but reads the calibration document line by line.
+pub fn process_reader<R: BufRead>(reader: R) -> io::Result<u64> {
+    stream::sum_lines(reader, process_line)
+}
+
+/// Same as [`process`], but reads the calibration document in `chunk_size`-byte chunks
+/// that are processed in parallel.
+pub fn process_chunked<R: Read>(reader: R, chunk_size: usize) -> io::Result<u64> {
+    stream::par_sum_chunks(reader, chunk_size, process_line)
+}
+
+fn process_line(line: &str) -> u32 {
+    let mut digits = line.chars().filter_map(char_to_digit);
+    let first = digits.next().expect("invalid input");
+    let last = digits.next_back().unwrap_or(first);
+
+    (first * 10 + last) as u32
 }
 
 fn char_to_digit(ch: char) -> Option<u8> {
@@ -22,12 +36,33 @@ fn char_to_digit(ch: char) -> Option<u8> {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_process() {
-        let input = "1abc2
+    use rstest::rstest;
+
+    const INPUT: &str = "1abc2
         pqr3stu8vwx
         a1b2c3d4e5f
         treb7uchet";
-        assert_eq!(process(input), 142);
+
+    #[test]
+    fn test_process() {
+        assert_eq!(process(INPUT), 142);
+    }
+
+    #[test]
+    fn test_process_reader() {
+        assert_eq!(process_reader(INPUT.as_bytes()).unwrap(), 142);
+        assert_eq!(
+            process_reader(INPUT.replace('\n', "\r\n").as_bytes()).unwrap(),
+            142
+        );
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(7)]
+    #[case(16)]
+    #[case(1024)]
+    fn test_process_chunked(#[case] chunk_size: usize) {
+        assert_eq!(process_chunked(INPUT.as_bytes(), chunk_size).unwrap(), 142);
     }
 }
@@ -1,7 +1,22 @@
+use std::io::{self, BufRead, Read};
+
+use crate::stream;
+
 pub fn process(input: &str) -> u32 {
     input.lines().map(process_line).sum()
 }
 
+/// Same as [`process`], but reads the calibration document line by line.
+pub fn process_reader<R: BufRead>(reader: R) -> io::Result<u64> {
+    stream::sum_lines(reader, process_line)
+}
+
+/// Same as [`process`], but reads the calibration document in `chunk_size`-byte chunks
+/// that are processed in parallel.
+pub fn process_chunked<R: Read>(reader: R, chunk_size: usize) -> io::Result<u64> {
+    stream::par_sum_chunks(reader, chunk_size, process_line)
+}
+
 fn process_line(line: &str) -> u32 {
     let mut digits = Digits::new(line);
     let first = digits.next().expect("invalid input");
@@ -10,16 +25,22 @@ fn process_line(line: &str) -> u32 {
     (first * 10 + last) as u32
 }
 
-struct Digits<'a> {
-    input: &'a str,
+/// Iterator over the digits spelled out in a line, either as `1`..=`9` or as `one`..=`nine`.
+///
+/// Spelled-out digits may overlap (`twone` yields `2` and `1`).
+/// `next` and `next_back` share the line between them and never yield the same digit twice.
+pub struct Digits<'a> {
+    input: &'a [u8],
+    /// Lowest byte offset a digit yielded by `next` may start at.
     start: usize,
+    /// Highest byte offset a digit yielded by `next_back` may end at.
     end: usize,
 }
 
 impl<'a> Digits<'a> {
-    fn new(input: &'a str) -> Self {
+    pub fn new(input: &'a str) -> Self {
         Self {
-            input,
+            input: input.as_bytes(),
             start: 0,
             end: input.len(),
         }
@@ -30,8 +51,8 @@ impl<'a> Iterator for Digits<'a> {
     type Item = u8;
 
     fn next(&mut self) -> Option<Self::Item> {
-        for i in self.start..self.input.len() {
-            let Some(digit) = digit_at_start(&self.input[i..]) else {
+        for i in self.start..self.end {
+            let Some(digit) = digit_at_start(&self.input[i..self.end]) else {
                 continue;
             };
             self.start = i + 1;
@@ -43,11 +64,11 @@ impl<'a> Iterator for Digits<'a> {
 
 impl<'a> DoubleEndedIterator for Digits<'a> {
     fn next_back(&mut self) -> Option<Self::Item> {
-        for j in (1..=self.end).rev() {
-            let Some(digit) = digit_at_end(&self.input[..j]) else {
+        for j in (self.start + 1..=self.end).rev() {
+            let Some(digit) = digit_at_end(&self.input[self.start..j]) else {
                 continue;
             };
-            self.end = j;
+            self.end = j - 1;
             return Some(digit);
         }
         None
@@ -75,22 +96,48 @@ const CONVERSIONS: [(&str, u8); 18] = [
     ("nine", 9),
 ];
 
-fn digit_at_start(input: &str) -> Option<u8> {
+fn digit_at_start(input: &[u8]) -> Option<u8> {
     CONVERSIONS
         .into_iter()
-        .find_map(|(s, c)| input.starts_with(s).then_some(c))
+        .find_map(|(s, c)| input.starts_with(s.as_bytes()).then_some(c))
 }
 
-fn digit_at_end(input: &str) -> Option<u8> {
+fn digit_at_end(input: &[u8]) -> Option<u8> {
     CONVERSIONS
         .into_iter()
-        .find_map(|(s, c)| input.ends_with(s).then_some(c))
+        .find_map(|(s, c)| input.ends_with(s.as_bytes()).then_some(c))
+}
+
+/// Naive reference of [`Digits`]: tries the digit pattern at every byte offset of the line.
+///
+/// Shared by the property tests and the fuzz target, which enables the `reference` feature.
+#[doc(hidden)]
+#[cfg(any(test, feature = "reference"))]
+pub fn reference_digits(line: &str) -> Vec<u8> {
+    let digit =
+        regex::Regex::new("^(?:[1-9]|one|two|three|four|five|six|seven|eight|nine)").unwrap();
+    (0..line.len())
+        .filter_map(|i| digit.find(line.get(i..)?))
+        .map(|m| match m.as_str() {
+            "one" => 1,
+            "two" => 2,
+            "three" => 3,
+            "four" => 4,
+            "five" => 5,
+            "six" => 6,
+            "seven" => 7,
+            "eight" => 8,
+            "nine" => 9,
+            d => d.parse().unwrap(),
+        })
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use proptest::prelude::*;
     use rstest::rstest;
 
     #[rstest]
@@ -109,15 +156,83 @@ mod tests {
         assert_eq!(process_line(input), expected);
     }
 
-    #[test]
-    fn test_process() {
-        let input = "two1nine
+    const INPUT: &str = "two1nine
         eightwothree
         abcone2threexyz
         xtwone3four
         4nineeightseven2
         zoneight234
         7pqrstsixteen";
-        assert_eq!(process(input), 281);
+
+    #[test]
+    fn test_process() {
+        assert_eq!(process(INPUT), 281);
+    }
+
+    #[test]
+    fn test_process_reader() {
+        assert_eq!(process_reader(INPUT.as_bytes()).unwrap(), 281);
+        assert_eq!(
+            process_reader(INPUT.replace('\n', "\r\n").as_bytes()).unwrap(),
+            281
+        );
+    }
+
+    #[rstest]
+    #[case(1)]
+    #[case(5)]
+    #[case(32)]
+    #[case(1024)]
+    fn test_process_chunked(#[case] chunk_size: usize) {
+        assert_eq!(process_chunked(INPUT.as_bytes(), chunk_size).unwrap(), 281);
+    }
+
+    fn line() -> impl Strategy<Value = String> {
+        prop_oneof![
+            prop::collection::vec(
+                prop_oneof![
+                    "one|two|three|four|five|six|seven|eight|nine",
+                    "[0-9]",
+                    "[a-z]{1,3}",
+                ],
+                0..16,
+            )
+            .prop_map(|parts| parts.concat()),
+            any::<String>(),
+        ]
+    }
+
+    proptest! {
+        #[test]
+        fn digits_match_reference(line in line()) {
+            prop_assert_eq!(Digits::new(&line).collect::<Vec<_>>(), reference_digits(&line));
+        }
+
+        #[test]
+        fn digits_forward_equals_reversed_backward(line in line()) {
+            let forward: Vec<_> = Digits::new(&line).collect();
+            let mut backward: Vec<_> = Digits::new(&line).rev().collect();
+            backward.reverse();
+            prop_assert_eq!(forward, backward);
+        }
+
+        #[test]
+        fn digits_ends_never_cross(line in line(), from_back in prop::collection::vec(any::<bool>(), 0..32)) {
+            let mut digits = Digits::new(&line);
+            let mut front = Vec::new();
+            let mut back = Vec::new();
+            for from_back in from_back.into_iter().chain(std::iter::repeat(false)) {
+                let next = if from_back { digits.next_back() } else { digits.next() };
+                match (next, from_back) {
+                    (Some(digit), false) => front.push(digit),
+                    (Some(digit), true) => back.push(digit),
+                    (None, _) => break,
+                }
+            }
+            prop_assert_eq!(digits.next(), None);
+            prop_assert_eq!(digits.next_back(), None);
+            front.extend(back.into_iter().rev());
+            prop_assert_eq!(front, reference_digits(&line));
+        }
     }
 }
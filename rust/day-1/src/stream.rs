@@ -0,0 +1,104 @@
+use std::io::{self, BufRead, Read};
+
+use rayon::prelude::*;
+
+/// Sums `process_line` over every line of `reader`, keeping only one line in memory at a time.
+///
+/// The sum is a `u64`, as a `u32` overflows after about 43 million lines.
+pub(crate) fn sum_lines<R: BufRead>(
+    mut reader: R,
+    process_line: impl Fn(&str) -> u32,
+) -> io::Result<u64> {
+    let mut line = String::new();
+    let mut sum = 0;
+    while reader.read_line(&mut line)? != 0 {
+        sum += u64::from(process_line(line.trim_end_matches(['\r', '\n'])));
+        line.clear();
+    }
+    Ok(sum)
+}
+
+/// Reads `reader` in batches of roughly `chunk_size`-byte chunks (one per rayon thread),
+/// cuts every chunk at its last newline and sums `process_line` over the chunks in parallel.
+///
+/// A line longer than `chunk_size` grows its chunk until the line ends.
+pub(crate) fn par_sum_chunks<R: Read>(
+    mut reader: R,
+    chunk_size: usize,
+    process_line: impl Fn(&str) -> u32 + Sync,
+) -> io::Result<u64> {
+    assert!(chunk_size > 0, "chunk size must be positive");
+
+    let batch_size = rayon::current_num_threads();
+    let mut carry = Vec::new();
+    let mut sum = 0;
+    loop {
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut eof = false;
+        while batch.len() < batch_size && !eof {
+            let mut chunk = std::mem::take(&mut carry);
+            let start = chunk.len();
+            chunk.resize(start + chunk_size, 0);
+            let read = read_full(&mut reader, &mut chunk[start..])?;
+            chunk.truncate(start + read);
+            eof = read < chunk_size;
+
+            if eof {
+                batch.push(chunk);
+            } else if let Some(i) = chunk.iter().rposition(|&b| b == b'\n') {
+                carry = chunk.split_off(i + 1);
+                batch.push(chunk);
+            } else {
+                carry = chunk;
+            }
+        }
+
+        sum += batch
+            .par_iter()
+            .map(|chunk| {
+                std::str::from_utf8(chunk)
+                    .map(|chunk| {
+                        chunk
+                            .lines()
+                            .map(|line| u64::from(process_line(line)))
+                            .sum::<u64>()
+                    })
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            })
+            .try_reduce(|| 0, |a, b| Ok(a + b))?;
+
+        if eof {
+            return Ok(sum);
+        }
+    }
+}
+
+/// Fills `buf` from `reader`, stopping early only at EOF.
+fn read_full<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_exceeds_u32() {
+        let input = "a\nb\nc\n";
+        let expected = 3 * u64::from(u32::MAX);
+        assert_eq!(expected, sum_lines(input.as_bytes(), |_| u32::MAX).unwrap());
+        assert_eq!(
+            expected,
+            par_sum_chunks(input.as_bytes(), 2, |_| u32::MAX).unwrap()
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Run with `cargo test -p day-1 --features dhat-heap --test alloc_budget`.
+#![cfg(feature = "dhat-heap")]
+
+use common::alloc_budget::{check, Budget};
+use day_1::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &str = include_str!("../input.txt");
+
+const PART1: Budget<u32> = Budget {
+    answer: 54630,
+    total_blocks: 0,
+    max_bytes: 0,
+};
+const PART2: Budget<u32> = Budget {
+    answer: 54770,
+    total_blocks: 0,
+    max_bytes: 0,
+};
+
+#[test]
+fn process_stays_within_budget() {
+    check("part1", INPUT, PART1, part1::process);
+    check("part2", INPUT, PART2, part2::process);
+}
@@ -5,6 +5,8 @@ fn main() {
     divan::main();
 }
 
+const GENERATED_GAMES: usize = 100_000;
+
 #[divan::bench]
 fn part1() {
     part1::process(divan::black_box(include_str!("../input.txt",)));
@@ -14,3 +16,31 @@ fn part1() {
 fn part2() {
     part2::process(divan::black_box(include_str!("../input.txt",)));
 }
+
+#[divan::bench]
+fn part1_bytes() {
+    part1::process_bytes(divan::black_box(include_bytes!("../input.txt")));
+}
+
+#[divan::bench]
+fn part2_bytes() {
+    part2::process_bytes(divan::black_box(include_bytes!("../input.txt")));
+}
+
+#[divan::bench]
+fn part1_generated(bencher: divan::Bencher) {
+    let generated = generate::generate(&generate::Config {
+        games: GENERATED_GAMES,
+        ..Default::default()
+    });
+    bencher.bench(|| part1::process(divan::black_box(&generated.input)));
+}
+
+#[divan::bench]
+fn part2_generated(bencher: divan::Bencher) {
+    let generated = generate::generate(&generate::Config {
+        games: GENERATED_GAMES,
+        ..Default::default()
+    });
+    bencher.bench(|| part2::process(divan::black_box(&generated.input)));
+}
@@ -0,0 +1,117 @@
+use std::fmt;
+
+use crate::game::{hand, Color, Game};
+
+/// Amount of cubes of each color in the bag.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Bag {
+    pub red: usize,
+    pub green: usize,
+    pub blue: usize,
+}
+
+impl Bag {
+    #[inline(always)]
+    pub const fn new(red: usize, green: usize, blue: usize) -> Self {
+        Self { red, green, blue }
+    }
+
+    pub const fn get(&self, color: Color) -> usize {
+        match color {
+            Color::Red => self.red,
+            Color::Green => self.green,
+            Color::Blue => self.blue,
+        }
+    }
+
+    /// Checks whether every hand of the `game` could have been drawn from this bag,
+    /// reporting the first hand and color that could not.
+    pub fn check(&self, game: &Game<hand::Normalized>) -> Result<(), Violation> {
+        game.hands
+            .iter()
+            .enumerate()
+            .find_map(|(i, hand)| {
+                Color::ALL.into_iter().find_map(|color| {
+                    let amount = hand.amount(color);
+                    let limit = self.get(color);
+                    (amount > limit).then_some(Violation {
+                        hand: i,
+                        color,
+                        amount,
+                        limit,
+                    })
+                })
+            })
+            .map_or(Ok(()), Err)
+    }
+
+    pub const fn power(&self) -> usize {
+        self.red * self.green * self.blue
+    }
+
+    /// Amount of cubes of each color in either of the bags, whichever is greater.
+    pub fn max(self, other: Self) -> Self {
+        Self {
+            red: self.red.max(other.red),
+            green: self.green.max(other.green),
+            blue: self.blue.max(other.blue),
+        }
+    }
+
+    /// Whether this bag holds at least as many cubes of every color as the `other`.
+    pub const fn holds(&self, other: &Self) -> bool {
+        other.red <= self.red && other.green <= self.green && other.blue <= self.blue
+    }
+
+    pub fn allows(&self, game: &Game<hand::Normalized>) -> bool {
+        self.check(game).is_ok()
+    }
+}
+
+impl fmt::Display for Bag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} red, {} green, {} blue",
+            self.red, self.green, self.blue
+        )
+    }
+}
+
+/// A hand that shows more cubes of some color than there are in the bag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("hand {} shows {amount} {color} cubes, but the bag holds only {limit}", .hand + 1)]
+pub struct Violation {
+    /// Zero-based index of the hand within the game.
+    pub hand: usize,
+    pub color: Color,
+    pub amount: usize,
+    pub limit: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green", Ok(()))]
+    #[case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue", Ok(()))]
+    #[case(
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        Err(Violation { hand: 0, color: Color::Red, amount: 20, limit: 12 }),
+    )]
+    #[case(
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        Err(Violation { hand: 2, color: Color::Red, amount: 14, limit: 12 }),
+    )]
+    #[case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green", Ok(()))]
+    fn test_check(#[case] input: &str, #[case] expected: Result<(), Violation>) {
+        let game = Game::<hand::Normalized>::parse(input).unwrap().1;
+        let bag = Bag::new(12, 13, 14);
+        assert_eq!(expected, bag.check(&game));
+        assert_eq!(expected.is_ok(), bag.allows(&game));
+    }
+}
@@ -0,0 +1,54 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use day_2::{
+    estimate::{estimate_game, estimate_games, Config, Prior},
+    game::{hand, Game},
+};
+
+/// Estimates the bag contents of every game, and of a single bag shared by all games.
+#[derive(Debug, Parser)]
+struct Args {
+    /// How many cubes above the minimum bag to consider for every color
+    #[arg(long, default_value_t = 20)]
+    extra: usize,
+    /// Mean of the Poisson prior of every color; the prior is uniform if omitted
+    #[arg(long = "poisson-mean", value_name = "MEAN", value_parser = poisson)]
+    prior: Option<Prior>,
+    /// Puzzle input to read instead of the bundled one
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+fn poisson(s: &str) -> Result<Prior, String> {
+    let mean: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    Prior::poisson(mean).map_err(|e| e.to_string())
+}
+
+fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+    let config = Config {
+        extra: args.extra,
+        prior: args.prior.unwrap_or(Prior::Uniform),
+    };
+
+    let file = match &args.input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => include_str!("../../input.txt").to_owned(),
+    };
+    let games: Vec<_> = file
+        .lines()
+        .map(|line| {
+            Game::<hand::Normalized>::parse(line)
+                .expect("invalid input")
+                .1
+        })
+        .collect();
+
+    for game in &games {
+        println!("Game {}: {}", game.id, estimate_game(game, &config));
+    }
+    println!("All games: {}", estimate_games(&games, &config));
+
+    Ok(())
+}
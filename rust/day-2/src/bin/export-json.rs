@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use day_2::game::{hand, Game};
+
+/// Prints the parsed games of the input as a JSON array.
+#[derive(Debug, Parser)]
+struct Args {
+    /// Sum up the cubes of each color within every hand
+    #[arg(long)]
+    normalized: bool,
+    /// Puzzle input to read instead of the bundled one
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+
+    let file = match &args.input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => include_str!("../../input.txt").to_owned(),
+    };
+
+    let games = file.lines().map(|line| {
+        Game::<hand::Unnormalized>::parse(line)
+            .expect("invalid input")
+            .1
+    });
+    let stdout = std::io::stdout().lock();
+    if args.normalized {
+        let games: Vec<_> = games.map(Game::<hand::Unnormalized>::normalized).collect();
+        serde_json::to_writer_pretty(stdout, &games)?;
+    } else {
+        let games: Vec<_> = games.collect();
+        serde_json::to_writer_pretty(stdout, &games)?;
+    }
+    println!();
+
+    Ok(())
+}
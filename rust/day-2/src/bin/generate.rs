@@ -0,0 +1,90 @@
+use clap::{builder::RangedU64ValueParser, error::ErrorKind, CommandFactory, Parser, ValueEnum};
+use day_2::{
+    game::Color,
+    generate::{generate, Amounts, Config},
+};
+
+/// Prints a synthetic input to stdout and its answers to stderr.
+#[derive(Debug, Parser)]
+struct Args {
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+    #[arg(long, default_value_t = 100)]
+    games: usize,
+    /// Minimum amount of hands in a game
+    #[arg(long, default_value_t = 3, value_parser = positive())]
+    min_hands: usize,
+    /// Maximum amount of hands in a game
+    #[arg(long, default_value_t = 6, value_parser = positive())]
+    max_hands: usize,
+    /// Colors that may appear in a hand, comma-separated
+    #[arg(long, value_delimiter = ',', default_value = "red,green,blue", value_parser = color)]
+    colors: Vec<Color>,
+    /// Distribution of the amount of cubes of one color in a hand
+    #[arg(long, value_enum, default_value_t = Distribution::Uniform)]
+    distribution: Distribution,
+    /// Maximum amount of cubes of one color in a hand
+    #[arg(long, default_value_t = 20, value_parser = positive())]
+    max_amount: usize,
+    /// Probability of the geometric distribution to stop at every amount
+    #[arg(long, default_value_t = 0.2, value_parser = probability)]
+    p: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Distribution {
+    /// Every amount from 1 to the maximum is equally likely
+    Uniform,
+    /// Each next amount is `1 - p` times as likely as the previous one
+    Geometric,
+}
+
+fn positive() -> RangedU64ValueParser<usize> {
+    RangedU64ValueParser::new().range(1..)
+}
+
+fn color(s: &str) -> Result<Color, String> {
+    Color::ALL
+        .into_iter()
+        .find(|color| color.name() == s)
+        .ok_or_else(|| format!("unknown color {s:?}, expected red, green or blue"))
+}
+
+fn probability(s: &str) -> Result<f64, String> {
+    let p: f64 = s.parse().map_err(|e| format!("{e}"))?;
+    if (0.0..=1.0).contains(&p) {
+        Ok(p)
+    } else {
+        Err(format!("{p} is not between 0 and 1"))
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    if args.min_hands > args.max_hands {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "--min-hands must not exceed --max-hands",
+            )
+            .exit();
+    }
+
+    let generated = generate(&Config {
+        seed: args.seed,
+        games: args.games,
+        hands_per_game: args.min_hands..=args.max_hands,
+        colors: args.colors,
+        amounts: match args.distribution {
+            Distribution::Uniform => Amounts::Uniform(1..=args.max_amount),
+            Distribution::Geometric => Amounts::Geometric {
+                p: args.p,
+                max: args.max_amount,
+            },
+        },
+        ..Config::default()
+    });
+    println!("{}", generated.input);
+    eprintln!("Part 1: {}", generated.part1);
+    eprintln!("Part 2: {}", generated.part2);
+}
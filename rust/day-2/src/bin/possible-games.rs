@@ -0,0 +1,52 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use day_2::{
+    bag::Bag,
+    game::{hand, Game},
+    part1::PUZZLE_BAG,
+};
+
+/// Prints which games are possible with the given bag contents.
+#[derive(Debug, Parser)]
+struct Args {
+    /// Amount of red cubes in the bag
+    #[arg(long, default_value_t = PUZZLE_BAG.red)]
+    red: usize,
+    /// Amount of green cubes in the bag
+    #[arg(long, default_value_t = PUZZLE_BAG.green)]
+    green: usize,
+    /// Amount of blue cubes in the bag
+    #[arg(long, default_value_t = PUZZLE_BAG.blue)]
+    blue: usize,
+    /// Puzzle input to read instead of the bundled one
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+    let bag = Bag::new(args.red, args.green, args.blue);
+
+    let file = match &args.input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => include_str!("../../input.txt").to_owned(),
+    };
+
+    let mut sum = 0;
+    for line in file.lines() {
+        let game = Game::<hand::Normalized>::parse(line)
+            .expect("invalid input")
+            .1;
+        match bag.check(&game) {
+            Ok(()) => {
+                sum += game.id;
+                println!("Game {}: possible", game.id);
+            }
+            Err(violation) => println!("Game {}: impossible, {violation}", game.id),
+        }
+    }
+    println!("Sum of possible game IDs: {sum}");
+
+    Ok(())
+}
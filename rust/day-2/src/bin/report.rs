@@ -0,0 +1,6 @@
+use day_2::stats::Stats;
+
+fn main() {
+    let file = include_str!("../../input.txt");
+    print!("{}", Stats::from_input(file));
+}
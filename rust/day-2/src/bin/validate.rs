@@ -0,0 +1,33 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::Parser;
+use day_2::validate::{validate, Strictness};
+
+/// Reports anomalies in the input: malformed lines, repeated colors within a hand,
+/// duplicated or missing game IDs, zero amounts and empty hands.
+#[derive(Debug, Parser)]
+struct Args {
+    /// One of `lenient`, `normal` or `strict`
+    #[arg(long, default_value = "normal")]
+    strictness: Strictness,
+    /// Puzzle input to read instead of the bundled one
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+fn main() -> std::io::Result<ExitCode> {
+    let args = Args::parse();
+
+    let file = match &args.input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => include_str!("../../input.txt").to_owned(),
+    };
+
+    let report = validate(&file, args.strictness);
+    print!("{report}");
+    Ok(if report.has_errors() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
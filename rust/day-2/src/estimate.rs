@@ -0,0 +1,334 @@
+//! Inverse of part 1: estimation of the bag contents from the hands drawn from it.
+//!
+//! Every hand is treated as a draw without replacement from the bag, with the cubes put back
+//! before the next hand, so the likelihood of a bag is a product of multivariate
+//! hypergeometric probabilities. Bags are searched exhaustively, from the [minimum
+//! bag](Game::minimum_bag) up to [`Config::extra`] more cubes of each color shown.
+
+use std::fmt;
+
+use crate::{
+    bag::Bag,
+    game::{hand, Color, Game, Hand},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Prior {
+    /// Every bag in the searched range is equally likely.
+    Uniform,
+    /// The amount of cubes of every color is independently Poisson-distributed with this mean,
+    /// which is positive and finite, see [`Prior::poisson`].
+    Poisson { mean: f64 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, thiserror::Error)]
+#[error("the mean of the Poisson prior must be positive and finite, not {0}")]
+pub struct InvalidMean(pub f64);
+
+impl Prior {
+    pub fn poisson(mean: f64) -> Result<Self, InvalidMean> {
+        if mean.is_finite() && mean > 0.0 {
+            Ok(Self::Poisson { mean })
+        } else {
+            Err(InvalidMean(mean))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Config {
+    /// How many cubes above the minimum bag are considered for every color that was shown.
+    /// Colors that were never shown are assumed to be absent.
+    pub extra: usize,
+    pub prior: Prior,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            extra: 20,
+            prior: Prior::Uniform,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Estimate {
+    /// The smallest bag every hand could have been drawn from.
+    pub minimum: Bag,
+    /// The bag under which the hands are the most likely.
+    pub maximum_likelihood: Bag,
+    /// Whether the maximum likelihood bag lies on the upper edge of the searched range,
+    /// so that a larger bag might be even more likely.
+    pub truncated: bool,
+    /// The most probable bag under the prior.
+    pub maximum_a_posteriori: Bag,
+    /// Expected amount of cubes of every color, in the [`Color::ALL`] order, under the prior.
+    pub posterior_mean: [f64; 3],
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [red, green, blue] = self.posterior_mean;
+        write!(
+            f,
+            "minimum {}; maximum likelihood {}{}; maximum a posteriori {}; \
+             posterior mean {red:.2} red, {green:.2} green, {blue:.2} blue",
+            self.minimum,
+            self.maximum_likelihood,
+            if self.truncated { " (truncated)" } else { "" },
+            self.maximum_a_posteriori,
+        )
+    }
+}
+
+/// Estimates the bag a single game was played with.
+///
+/// # Panics
+///
+/// If the prior is a Poisson distribution whose mean is not positive and finite.
+pub fn estimate_game(game: &Game<hand::Normalized>, config: &Config) -> Estimate {
+    estimate(&game.hands, config)
+}
+
+/// Estimates a single bag all the `games` were played with.
+///
+/// # Panics
+///
+/// If the prior is a Poisson distribution whose mean is not positive and finite.
+pub fn estimate_games<'a>(
+    games: impl IntoIterator<Item = &'a Game<hand::Normalized>>,
+    config: &Config,
+) -> Estimate {
+    let hands: Vec<&Hand<hand::Normalized>> =
+        games.into_iter().flat_map(|game| &game.hands).collect();
+    estimate(hands, config)
+}
+
+fn estimate<'a>(
+    hands: impl IntoIterator<Item = &'a Hand<hand::Normalized>>,
+    config: &Config,
+) -> Estimate {
+    if let Prior::Poisson { mean } = config.prior {
+        // every log-density would be NaN otherwise
+        if let Err(e) = Prior::poisson(mean) {
+            panic!("{e}");
+        }
+    }
+    let hands: Vec<[usize; 3]> = hands
+        .into_iter()
+        .map(|hand| Color::ALL.map(|color| hand.amount(color)))
+        .collect();
+    let minimum = hands
+        .iter()
+        .fold([0; 3], |acc, hand| [0, 1, 2].map(|i| acc[i].max(hand[i])));
+    let maximum = minimum.map(|amount| if amount > 0 { amount + config.extra } else { 0 });
+    let ln_factorials = LnFactorials::new(maximum.iter().sum());
+
+    let mut best_likelihood = (f64::NEG_INFINITY, minimum);
+    let mut best_posterior = (f64::NEG_INFINITY, minimum);
+    // (log-density, bag) of every bag in the range, for the posterior mean
+    let mut posterior = Vec::new();
+    for red in minimum[0]..=maximum[0] {
+        for green in minimum[1]..=maximum[1] {
+            for blue in minimum[2]..=maximum[2] {
+                let bag = [red, green, blue];
+                let ln_likelihood: f64 = hands
+                    .iter()
+                    .map(|hand| ln_factorials.ln_hypergeometric(bag, *hand))
+                    .sum();
+                let ln_posterior = ln_likelihood + ln_prior(config.prior, bag, &ln_factorials);
+                if ln_likelihood > best_likelihood.0 {
+                    best_likelihood = (ln_likelihood, bag);
+                }
+                if ln_posterior > best_posterior.0 {
+                    best_posterior = (ln_posterior, bag);
+                }
+                posterior.push((ln_posterior, bag));
+            }
+        }
+    }
+
+    let normalization: f64 = posterior
+        .iter()
+        .map(|(ln_posterior, _)| (ln_posterior - best_posterior.0).exp())
+        .sum();
+    let posterior_mean = [0, 1, 2].map(|i| {
+        posterior
+            .iter()
+            .map(|(ln_posterior, bag)| (ln_posterior - best_posterior.0).exp() * bag[i] as f64)
+            .sum::<f64>()
+            / normalization
+    });
+
+    let to_bag = |[red, green, blue]: [usize; 3]| Bag::new(red, green, blue);
+    Estimate {
+        minimum: to_bag(minimum),
+        maximum_likelihood: to_bag(best_likelihood.1),
+        truncated: (0..3).any(|i| maximum[i] > minimum[i] && best_likelihood.1[i] == maximum[i]),
+        maximum_a_posteriori: to_bag(best_posterior.1),
+        posterior_mean,
+    }
+}
+
+/// Logarithm of the prior probability of the `bag`, up to a constant.
+fn ln_prior(prior: Prior, bag: [usize; 3], ln_factorials: &LnFactorials) -> f64 {
+    match prior {
+        Prior::Uniform => 0.0,
+        Prior::Poisson { mean } => bag
+            .into_iter()
+            .map(|amount| amount as f64 * mean.ln() - ln_factorials.get(amount))
+            .sum(),
+    }
+}
+
+/// Table of `ln(n!)`.
+struct LnFactorials(Vec<f64>);
+
+impl LnFactorials {
+    fn new(max: usize) -> Self {
+        let mut table = Vec::with_capacity(max + 1);
+        table.push(0.0);
+        for n in 1..=max {
+            table.push(table[n - 1] + (n as f64).ln());
+        }
+        Self(table)
+    }
+
+    fn get(&self, n: usize) -> f64 {
+        self.0[n]
+    }
+
+    fn ln_binomial(&self, n: usize, k: usize) -> f64 {
+        if k > n {
+            return f64::NEG_INFINITY;
+        }
+        self.get(n) - self.get(k) - self.get(n - k)
+    }
+
+    /// Logarithm of the probability to draw the `hand` from the `bag` without replacement.
+    fn ln_hypergeometric(&self, bag: [usize; 3], hand: [usize; 3]) -> f64 {
+        (0..3)
+            .map(|i| self.ln_binomial(bag[i], hand[i]))
+            .sum::<f64>()
+            - self.ln_binomial(bag.iter().sum(), hand.iter().sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn game(input: &str) -> Game<hand::Normalized> {
+        Game::<hand::Normalized>::parse(input).unwrap().1
+    }
+
+    #[test]
+    fn test_ln_hypergeometric() {
+        let ln_factorials = LnFactorials::new(10);
+        // C(2, 1) * C(1, 1) / C(3, 2)
+        let probability = ln_factorials.ln_hypergeometric([2, 1, 0], [1, 1, 0]).exp();
+        assert!((probability - 2.0 / 3.0).abs() < 1e-12);
+        assert_eq!(
+            f64::NEG_INFINITY,
+            ln_factorials.ln_hypergeometric([1, 1, 0], [2, 0, 0])
+        );
+    }
+
+    #[rstest]
+    #[case("Game 1: 4 red, 3 blue", Bag::new(4, 0, 3))]
+    #[case("Game 2: 2 red, 1 green; 1 green, 2 red", Bag::new(2, 1, 0))]
+    fn test_hands_drawing_the_whole_bag(#[case] input: &str, #[case] expected: Bag) {
+        let estimate = estimate_game(&game(input), &Config::default());
+        assert_eq!(expected, estimate.minimum);
+        assert_eq!(expected, estimate.maximum_likelihood);
+        assert_eq!(expected, estimate.maximum_a_posteriori);
+        assert!(!estimate.truncated);
+    }
+
+    #[test]
+    fn test_larger_bag_is_more_likely() {
+        // out of the minimum bag (2, 0, 2) every hand is only 1/6 likely,
+        // while out of (n, 0, n) it is (n - 1) / (4n - 2), approaching 1/4
+        let estimate = estimate_game(
+            &game("Game 1: 2 red; 2 blue; 2 red; 2 blue"),
+            &Config::default(),
+        );
+        assert_eq!(Bag::new(2, 0, 2), estimate.minimum);
+        assert!(estimate.maximum_likelihood.red > 2);
+        assert_eq!(
+            estimate.maximum_likelihood.red,
+            estimate.maximum_likelihood.blue
+        );
+        assert_eq!(0, estimate.maximum_likelihood.green);
+        assert!(estimate.truncated);
+    }
+
+    #[test]
+    fn test_prior() {
+        let game = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let uniform = estimate_game(&game, &Config::default());
+        assert_eq!(uniform.maximum_likelihood, uniform.maximum_a_posteriori);
+
+        let small = estimate_game(
+            &game,
+            &Config {
+                prior: Prior::poisson(1.0).unwrap(),
+                ..Config::default()
+            },
+        );
+        let large = estimate_game(
+            &game,
+            &Config {
+                prior: Prior::poisson(30.0).unwrap(),
+                ..Config::default()
+            },
+        );
+        for i in 0..3 {
+            assert!(small.posterior_mean[i] >= [4.0, 2.0, 6.0][i]);
+            assert!(small.posterior_mean[i] < large.posterior_mean[i]);
+        }
+    }
+
+    #[rstest]
+    #[case(1.0, Ok(Prior::Poisson { mean: 1.0 }))]
+    #[case(0.5, Ok(Prior::Poisson { mean: 0.5 }))]
+    #[case(0.0, Err(InvalidMean(0.0)))]
+    #[case(-1.0, Err(InvalidMean(-1.0)))]
+    #[case(f64::INFINITY, Err(InvalidMean(f64::INFINITY)))]
+    fn test_poisson(#[case] mean: f64, #[case] expected: Result<Prior, InvalidMean>) {
+        assert_eq!(expected, Prior::poisson(mean));
+    }
+
+    #[test]
+    fn test_poisson_nan() {
+        assert!(Prior::poisson(f64::NAN).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "the mean of the Poisson prior must be positive and finite, not 0")]
+    fn test_estimate_invalid_mean() {
+        estimate_game(
+            &game("Game 1: 3 blue"),
+            &Config {
+                prior: Prior::Poisson { mean: 0.0 },
+                ..Config::default()
+            },
+        );
+    }
+
+    #[test]
+    fn test_estimate_games() {
+        let games = [
+            game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green"),
+            game("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue"),
+        ];
+        let estimate = estimate_games(&games, &Config::default());
+        assert_eq!(Bag::new(4, 3, 6), estimate.minimum);
+        assert!(estimate.minimum.red <= estimate.maximum_likelihood.red);
+        assert!(estimate.minimum.green <= estimate.maximum_likelihood.green);
+        assert!(estimate.minimum.blue <= estimate.maximum_likelihood.blue);
+    }
+}
@@ -1,3 +1,5 @@
+use std::fmt;
+
 use nom::{
     branch::alt,
     bytes::complete::tag,
@@ -8,8 +10,10 @@ use nom::{
     IResult,
 };
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+use crate::bag::Bag;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game<Normalization> {
     pub id: usize,
     pub hands: Vec<Hand<Normalization>>,
@@ -25,7 +29,11 @@ impl Game<hand::Unnormalized> {
     pub fn normalized(self) -> Game<hand::Normalized> {
         Game {
             id: self.id,
-            hands: self.hands.into_iter().map(Hand::normalized).collect(),
+            hands: self
+                .hands
+                .into_iter()
+                .map(Hand::<hand::Unnormalized>::normalized)
+                .collect(),
         }
     }
 }
@@ -34,22 +42,53 @@ impl Game<hand::Normalized> {
     pub fn parse(input: &str) -> IResult<&str, Self> {
         Game::<hand::Unnormalized>::parse(input).map(|(input, game)| (input, game.normalized()))
     }
+
+    /// The smallest bag every hand of the game could have been drawn from.
+    pub fn minimum_bag(&self) -> Bag {
+        self.hands.iter().fold(Bag::default(), |acc, Hand(hand)| {
+            acc.max(Bag::new(
+                hand.red_amount,
+                hand.green_amount,
+                hand.blue_amount,
+            ))
+        })
+    }
+}
+
+/// Prints the game in the puzzle format, `Game N: a color, b color; ...`,
+/// so that parsing the output yields the same game.
+impl<Normalization> fmt::Display for Game<Normalization>
+where
+    Hand<Normalization>: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, hand) in self.hands.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{hand}")?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct Hand<Normalization>(pub Normalization);
 
 pub mod hand {
     use super::Cubes;
 
-    #[derive(Debug)]
-    #[cfg_attr(test, derive(PartialEq, Eq))]
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    #[cfg_attr(feature = "serde", serde(transparent))]
     #[repr(transparent)]
     pub struct Unnormalized(pub Vec<Cubes>);
 
-    #[derive(Debug, Default)]
-    #[cfg_attr(test, derive(PartialEq, Eq))]
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub struct Normalized {
         pub red_amount: usize,
         pub green_amount: usize,
@@ -86,6 +125,18 @@ impl Hand<hand::Unnormalized> {
     }
 }
 
+impl fmt::Display for Hand<hand::Unnormalized> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, cubes) in self.0 .0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{cubes}")?;
+        }
+        Ok(())
+    }
+}
+
 impl Hand<hand::Normalized> {
     #[inline(always)]
     pub fn new_normalized(red: usize, green: usize, blue: usize) -> Self {
@@ -95,10 +146,40 @@ impl Hand<hand::Normalized> {
             blue_amount: blue,
         })
     }
+
+    pub const fn amount(&self, color: Color) -> usize {
+        match color {
+            Color::Red => self.0.red_amount,
+            Color::Green => self.0.green_amount,
+            Color::Blue => self.0.blue_amount,
+        }
+    }
+}
+
+/// Prints the colors present in the hand in the red, green, blue order,
+/// or all of them if the hand is empty.
+impl fmt::Display for Hand<hand::Normalized> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let empty = Color::ALL.into_iter().all(|color| self.amount(color) == 0);
+        let cubes = Color::ALL
+            .into_iter()
+            .map(|color| Cubes {
+                amount: self.amount(color),
+                color,
+            })
+            .filter(|cubes| empty || cubes.amount > 0);
+        for (i, cubes) in cubes.enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{cubes}")?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Debug)]
-#[cfg_attr(test, derive(PartialEq, Eq))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Cubes {
     pub amount: usize,
     pub color: Color,
@@ -113,11 +194,19 @@ impl Cubes {
     }
 }
 
-fn usize(input: &str) -> IResult<&str, usize> {
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount, self.color)
+    }
+}
+
+pub(crate) fn usize(input: &str) -> IResult<&str, usize> {
     map_res(digit1, <str>::parse)(input)
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Color {
     Red,
     Green,
@@ -125,6 +214,16 @@ pub enum Color {
 }
 
 impl Color {
+    pub const ALL: [Self; 3] = [Self::Red, Self::Green, Self::Blue];
+
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Red => "red",
+            Self::Green => "green",
+            Self::Blue => "blue",
+        }
+    }
+
     pub fn parse(input: &str) -> IResult<&str, Self> {
         alt((
             value(Self::Red, tag("red")),
@@ -134,10 +233,17 @@ impl Color {
     }
 }
 
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use proptest::prelude::*;
     use rstest::rstest;
 
     #[rstest]
@@ -308,4 +414,100 @@ mod tests {
     ) {
         assert_eq!(expected, input.normalized());
     }
+
+    #[rstest]
+    #[case(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+        Bag::new(4, 2, 6)
+    )]
+    #[case(
+        "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+        Bag::new(1, 3, 4)
+    )]
+    #[case(
+        "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+        Bag::new(20, 13, 6)
+    )]
+    #[case(
+        "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+        Bag::new(14, 3, 15)
+    )]
+    #[case(
+        "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        Bag::new(6, 3, 2)
+    )]
+    fn test_minimum_bag(#[case] input: &str, #[case] expected: Bag) {
+        assert_eq!(
+            expected,
+            Game::<hand::Normalized>::parse(input)
+                .unwrap()
+                .1
+                .minimum_bag()
+        );
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green")]
+    #[case("Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue")]
+    #[case("Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red")]
+    #[case("Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red")]
+    #[case("Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green")]
+    #[case("Game 6: 0 red, 3 red")]
+    fn test_display_round_trip(#[case] input: &str) {
+        let game = Game::<hand::Unnormalized>::parse(input).unwrap().1;
+        assert_eq!(input, game.to_string());
+
+        let normalized = game.normalized();
+        assert_eq!(
+            normalized,
+            Game::<hand::Normalized>::parse(&normalized.to_string())
+                .unwrap()
+                .1
+        );
+    }
+
+    #[test]
+    fn test_display_normalized() {
+        let game = Game::<hand::Normalized>::parse("Game 1: 3 blue, 4 red, 1 blue; 0 green")
+            .unwrap()
+            .1;
+        assert_eq!(
+            "Game 1: 4 red, 4 blue; 0 red, 0 green, 0 blue",
+            game.to_string()
+        );
+    }
+
+    fn game() -> impl Strategy<Value = Game<hand::Unnormalized>> {
+        let color = prop_oneof![Just(Color::Red), Just(Color::Green), Just(Color::Blue)];
+        let cubes = (0..100usize, color).prop_map(|(amount, color)| Cubes { amount, color });
+        let hand = prop::collection::vec(cubes, 1..5).prop_map(Hand::new_unnormalized);
+        (0..1000usize, prop::collection::vec(hand, 1..8)).prop_map(|(id, hands)| Game { id, hands })
+    }
+
+    proptest! {
+        #[test]
+        fn parse_inverts_display(game in game()) {
+            prop_assert_eq!(&game, &Game::<hand::Unnormalized>::parse(&game.to_string()).unwrap().1);
+
+            let normalized = game.normalized();
+            prop_assert_eq!(
+                &normalized,
+                &Game::<hand::Normalized>::parse(&normalized.to_string()).unwrap().1
+            );
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_round_trip() {
+        let game = Game::<hand::Unnormalized>::parse("Game 1: 3 blue, 4 red; 2 green")
+            .unwrap()
+            .1;
+        let json = serde_json::to_string(&game).unwrap();
+        assert_eq!(
+            r#"{"id":1,"hands":[[{"amount":3,"color":"blue"},{"amount":4,"color":"red"}],[{"amount":2,"color":"green"}]]}"#,
+            json
+        );
+        assert_eq!(game, serde_json::from_str(&json).unwrap());
+    }
 }
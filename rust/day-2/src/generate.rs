@@ -0,0 +1,189 @@
+//! Seeded generator of synthetic inputs with known answers.
+
+use std::{fmt::Write, ops::RangeInclusive};
+
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    bag::Bag,
+    game::{Color, Cubes},
+    part1::PUZZLE_BAG,
+};
+
+/// Distribution of the amount of cubes of one color in a hand.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Amounts {
+    Uniform(RangeInclusive<usize>),
+    /// Amounts of at least 1, each next one `1 - p` times as likely as the previous,
+    /// capped at `max`.
+    Geometric {
+        p: f64,
+        max: usize,
+    },
+}
+
+impl Amounts {
+    fn sample(&self, rng: &mut impl Rng) -> usize {
+        match self {
+            Self::Uniform(range) => rng.gen_range(range.clone()),
+            Self::Geometric { p, max } => {
+                let mut amount = 1;
+                while amount < *max && !rng.gen_bool(*p) {
+                    amount += 1;
+                }
+                amount
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub seed: u64,
+    pub games: usize,
+    pub hands_per_game: RangeInclusive<usize>,
+    /// Colors that may appear in a hand. Every hand shows at least one of them, and
+    /// each of them at most once, so repeating a color changes nothing.
+    pub colors: Vec<Color>,
+    pub amounts: Amounts,
+    /// Bag the expected answer for part 1 is computed against.
+    pub bag: Bag,
+}
+
+impl Default for Config {
+    /// Roughly the shape of a real puzzle input.
+    fn default() -> Self {
+        Self {
+            seed: 0,
+            games: 100,
+            hands_per_game: 3..=6,
+            colors: Color::ALL.to_vec(),
+            amounts: Amounts::Uniform(1..=20),
+            bag: PUZZLE_BAG,
+        }
+    }
+}
+
+/// Generated input along with the answers to both parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    pub part1: usize,
+    pub part2: usize,
+}
+
+pub fn generate(config: &Config) -> Generated {
+    assert!(!config.colors.is_empty(), "at least one color is required");
+    assert!(
+        *config.hands_per_game.start() > 0,
+        "every game needs at least one hand"
+    );
+
+    let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+    let mut colors = Vec::with_capacity(config.colors.len());
+    for &color in &config.colors {
+        if !colors.contains(&color) {
+            colors.push(color);
+        }
+    }
+    let mut generated = Generated {
+        input: String::new(),
+        part1: 0,
+        part2: 0,
+    };
+
+    for id in 1..=config.games {
+        if id > 1 {
+            generated.input.push('\n');
+        }
+        write!(generated.input, "Game {id}: ").unwrap();
+
+        let mut minimum = Bag::default();
+        for i in 0..rng.gen_range(config.hands_per_game.clone()) {
+            if i > 0 {
+                generated.input.push_str("; ");
+            }
+
+            colors.shuffle(&mut rng);
+            let shown = rng.gen_range(1..=colors.len());
+            let mut hand = Bag::default();
+            for (j, &color) in colors[..shown].iter().enumerate() {
+                if j > 0 {
+                    generated.input.push_str(", ");
+                }
+                let amount = config.amounts.sample(&mut rng);
+                write!(generated.input, "{}", Cubes { amount, color }).unwrap();
+                match color {
+                    Color::Red => hand.red = amount,
+                    Color::Green => hand.green = amount,
+                    Color::Blue => hand.blue = amount,
+                }
+            }
+            minimum = minimum.max(hand);
+        }
+
+        if config.bag.holds(&minimum) {
+            generated.part1 += id;
+        }
+        generated.part2 += minimum.power();
+    }
+
+    generated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::{part1, part2, validate};
+
+    #[rstest]
+    #[case(Config::default())]
+    #[case(Config { seed: 7, games: 1000, ..Config::default() })]
+    #[case(Config { hands_per_game: 1..=1, colors: vec![Color::Green, Color::Blue], ..Config::default() })]
+    #[case(Config { colors: vec![Color::Red, Color::Blue, Color::Red, Color::Red], ..Config::default() })]
+    #[case(Config { amounts: Amounts::Geometric { p: 0.2, max: 30 }, bag: Bag::new(5, 5, 5), ..Config::default() })]
+    fn test_answers_match_solutions(#[case] config: Config) {
+        let generated = generate(&config);
+        assert_eq!(config.games, generated.input.lines().count());
+        assert_eq!(
+            generated.part1,
+            part1::process_with_bag(&generated.input, &config.bag)
+        );
+        assert_eq!(generated.part2, part2::process(&generated.input));
+        assert!(
+            validate::validate(&generated.input, validate::Strictness::Strict)
+                .diagnostics
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_repeated_colors() {
+        let repeated = Config {
+            colors: vec![Color::Green, Color::Green, Color::Blue, Color::Green],
+            ..Config::default()
+        };
+        let distinct = Config {
+            colors: vec![Color::Green, Color::Blue],
+            ..Config::default()
+        };
+        assert_eq!(generate(&distinct), generate(&repeated));
+    }
+
+    #[test]
+    fn test_seeded() {
+        assert_eq!(generate(&Config::default()), generate(&Config::default()));
+        assert_ne!(
+            generate(&Config::default()).input,
+            generate(&Config {
+                seed: 1,
+                ..Config::default()
+            })
+            .input
+        );
+    }
+}
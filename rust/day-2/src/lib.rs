@@ -1,3 +1,10 @@
+pub mod bag;
+pub mod estimate;
+pub mod game;
+pub mod generate;
+pub mod open;
 pub mod part1;
 pub mod part2;
-pub mod game;
\ No newline at end of file
+pub mod scan;
+pub mod stats;
+pub mod validate;
@@ -0,0 +1,357 @@
+//! Game model over an open palette of colors.
+//!
+//! Colors are interned into a [`Palette`], and a normalized hand is a per-color count map.
+//! The three-color [`crate::game::hand::Normalized`] stays the fast path for the puzzle itself.
+
+use std::collections::HashMap;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, char},
+    combinator::map,
+    error::{Error, ErrorKind},
+    multi::separated_list1,
+    sequence::{delimited, separated_pair},
+    Err, IResult,
+};
+
+use crate::game::{self, usize, Game, Hand};
+
+/// Index of a color interned in a [`Palette`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ColorId(usize);
+
+impl ColorId {
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Palette {
+    names: Vec<Box<str>>,
+    ids: HashMap<Box<str>, ColorId>,
+    closed: bool,
+}
+
+impl Palette {
+    /// An open palette that interns every color it meets.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A closed palette that only knows the `names`.
+    pub fn declared<'a>(names: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut palette = Self::new();
+        for name in names {
+            palette.intern(name);
+        }
+        palette.closed = true;
+        palette
+    }
+
+    /// The closed palette of the puzzle, with the ids of red, green and blue following
+    /// [`game::Color::ALL`].
+    pub fn rgb() -> Self {
+        Self::declared(game::Color::ALL.map(game::Color::name))
+    }
+
+    /// Returns the id of the color `name`, interning it if the palette is open.
+    pub fn intern(&mut self, name: &str) -> Option<ColorId> {
+        if let Some(&id) = self.ids.get(name) {
+            return Some(id);
+        }
+        if self.closed {
+            return None;
+        }
+        let id = ColorId(self.names.len());
+        self.names.push(name.into());
+        self.ids.insert(name.into(), id);
+        Some(id)
+    }
+
+    pub fn get(&self, name: &str) -> Option<ColorId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, color: ColorId) -> &str {
+        &self.names[color.0]
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn colors(&self) -> impl Iterator<Item = ColorId> {
+        (0..self.names.len()).map(ColorId)
+    }
+}
+
+pub mod hand {
+    use super::{ColorId, Cubes};
+
+    #[derive(Debug)]
+    #[cfg_attr(test, derive(PartialEq, Eq))]
+    #[repr(transparent)]
+    pub struct Unnormalized(pub Vec<Cubes>);
+
+    /// Amount of cubes per color. Colors past the end of the map have no cubes.
+    #[derive(Debug, Default, Clone, PartialEq, Eq)]
+    #[repr(transparent)]
+    pub struct Normalized(pub Vec<usize>);
+
+    impl Normalized {
+        pub fn get(&self, color: ColorId) -> usize {
+            self.0.get(color.index()).copied().unwrap_or(0)
+        }
+
+        pub fn add(&mut self, color: ColorId, amount: usize) {
+            if self.0.len() <= color.index() {
+                self.0.resize(color.index() + 1, 0);
+            }
+            self.0[color.index()] += amount;
+        }
+
+        /// Raises every count to at least the one in `other`.
+        pub fn max_assign(&mut self, other: &Self) {
+            if self.0.len() < other.0.len() {
+                self.0.resize(other.0.len(), 0);
+            }
+            for (a, &b) in self.0.iter_mut().zip(&other.0) {
+                *a = (*a).max(b);
+            }
+        }
+
+        pub fn iter(&self) -> impl Iterator<Item = (ColorId, usize)> + '_ {
+            self.0
+                .iter()
+                .enumerate()
+                .map(|(i, &amount)| (ColorId(i), amount))
+        }
+    }
+}
+
+impl Game<hand::Unnormalized> {
+    pub fn parse_with<'a>(input: &'a str, palette: &mut Palette) -> IResult<&'a str, Self> {
+        let (input, id) = delimited(tag("Game "), usize, tag(": "))(input)?;
+        let (input, hands) =
+            separated_list1(tag("; "), |input| Hand::parse_with(input, palette))(input)?;
+        Ok((input, Self { id, hands }))
+    }
+
+    pub fn normalized(self) -> Game<hand::Normalized> {
+        Game {
+            id: self.id,
+            hands: self
+                .hands
+                .into_iter()
+                .map(Hand::<hand::Unnormalized>::normalized)
+                .collect(),
+        }
+    }
+}
+
+impl Game<hand::Normalized> {
+    pub fn parse_with<'a>(input: &'a str, palette: &mut Palette) -> IResult<&'a str, Self> {
+        Game::<hand::Unnormalized>::parse_with(input, palette)
+            .map(|(input, game)| (input, game.normalized()))
+    }
+
+    /// The smallest bag every hand of the game could have been drawn from.
+    pub fn minimum_bag(&self) -> Bag {
+        Bag(self
+            .hands
+            .iter()
+            .fold(hand::Normalized::default(), |mut acc, Hand(hand)| {
+                acc.max_assign(hand);
+                acc
+            }))
+    }
+}
+
+impl Hand<hand::Unnormalized> {
+    pub fn parse_with<'a>(input: &'a str, palette: &mut Palette) -> IResult<&'a str, Self> {
+        map(
+            separated_list1(tag(", "), |input| Cubes::parse_with(input, palette)),
+            |cubes| Self(hand::Unnormalized(cubes)),
+        )(input)
+    }
+
+    pub fn normalized(self) -> Hand<hand::Normalized> {
+        Hand(self.0 .0.into_iter().fold(
+            hand::Normalized::default(),
+            |mut acc, Cubes { amount, color }| {
+                acc.add(color, amount);
+                acc
+            },
+        ))
+    }
+}
+
+impl From<Hand<game::hand::Normalized>> for Hand<hand::Normalized> {
+    /// Converts a three-color hand, with colors following [`Palette::rgb`].
+    fn from(Hand(hand): Hand<game::hand::Normalized>) -> Self {
+        Self(hand::Normalized(vec![
+            hand.red_amount,
+            hand.green_amount,
+            hand.blue_amount,
+        ]))
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(test, derive(PartialEq, Eq))]
+pub struct Cubes {
+    pub amount: usize,
+    pub color: ColorId,
+}
+
+impl Cubes {
+    pub fn parse_with<'a>(input: &'a str, palette: &mut Palette) -> IResult<&'a str, Self> {
+        let (rest, (amount, name)) = separated_pair(usize, char(' '), alpha1)(input)?;
+        let color = palette
+            .intern(name)
+            .ok_or_else(|| Err::Error(Error::new(name, ErrorKind::Verify)))?;
+        Ok((rest, Self { amount, color }))
+    }
+}
+
+/// Amount of cubes of each color in the bag. Colors missing from the bag have no cubes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Bag(pub hand::Normalized);
+
+impl Bag {
+    /// Builds a bag from `(color, amount)` pairs, interning the colors in the `palette`.
+    ///
+    /// Returns `None` if a color is not part of a closed palette.
+    pub fn from_amounts<'a>(
+        palette: &mut Palette,
+        amounts: impl IntoIterator<Item = (&'a str, usize)>,
+    ) -> Option<Self> {
+        let mut bag = hand::Normalized::default();
+        for (name, amount) in amounts {
+            bag.add(palette.intern(name)?, amount);
+        }
+        Some(Self(bag))
+    }
+
+    pub fn get(&self, color: ColorId) -> usize {
+        self.0.get(color)
+    }
+
+    pub fn allows(&self, game: &Game<hand::Normalized>) -> bool {
+        game.hands
+            .iter()
+            .all(|Hand(hand)| hand.iter().all(|(color, amount)| amount <= self.get(color)))
+    }
+
+    /// Product of the amounts of every color in the `palette`.
+    pub fn power(&self, palette: &Palette) -> usize {
+        palette.colors().map(|color| self.get(color)).product()
+    }
+}
+
+impl From<crate::bag::Bag> for Bag {
+    /// Converts a three-color bag, with colors following [`Palette::rgb`].
+    fn from(bag: crate::bag::Bag) -> Self {
+        Self(hand::Normalized(vec![bag.red, bag.green, bag.blue]))
+    }
+}
+
+/// Part 1 over any palette: the sum of IDs of the games possible with the `bag`.
+pub fn process_part1(input: &str, palette: &mut Palette, bag: &Bag) -> usize {
+    input
+        .lines()
+        .map(|line| {
+            Game::<hand::Normalized>::parse_with(line, palette)
+                .expect("invalid input")
+                .1
+        })
+        .filter(|game| bag.allows(game))
+        .map(|game| game.id)
+        .sum()
+}
+
+/// Part 2 over any palette: the sum of powers of the minimum bags of all games.
+///
+/// The power is taken over every color of the `palette`, once the whole input is parsed.
+pub fn process_part2(input: &str, palette: &mut Palette) -> usize {
+    let bags: Vec<Bag> = input
+        .lines()
+        .map(|line| {
+            Game::<hand::Normalized>::parse_with(line, palette)
+                .expect("invalid input")
+                .1
+                .minimum_bag()
+        })
+        .collect();
+    bags.iter().map(|bag| bag.power(palette)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::part1::PUZZLE_BAG;
+
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    const INPUT_WITH_YELLOW: &str =
+        "Game 1: 3 blue, 4 red, 1 yellow; 1 red, 2 green, 6 blue; 2 green, 2 yellow
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue, 5 yellow
+Game 3: 2 yellow, 2 red; 1 green, 1 blue";
+
+    #[test]
+    fn test_rgb_matches_typed() {
+        let mut palette = Palette::rgb();
+        assert_eq!(8, process_part1(INPUT, &mut palette, &PUZZLE_BAG.into()));
+        assert_eq!(2286, process_part2(INPUT, &mut palette));
+        assert_eq!(3, palette.len());
+    }
+
+    #[test]
+    fn test_open_palette() {
+        let mut palette = Palette::new();
+        let bag = Bag::from_amounts(
+            &mut palette,
+            [("red", 12), ("green", 13), ("blue", 14), ("yellow", 2)],
+        )
+        .unwrap();
+        assert_eq!(4, process_part1(INPUT_WITH_YELLOW, &mut palette, &bag));
+        // 4 * 2 * 6 * 2 + 1 * 3 * 4 * 5 + 2 * 1 * 1 * 2
+        assert_eq!(96 + 60 + 4, process_part2(INPUT_WITH_YELLOW, &mut palette));
+        assert_eq!(Some(ColorId(3)), palette.get("yellow"));
+    }
+
+    #[test]
+    fn test_closed_palette_rejects_unknown_color() {
+        let mut palette = Palette::rgb();
+        assert!(Game::<hand::Unnormalized>::parse_with("Game 1: 3 yellow", &mut palette).is_err());
+        assert!(palette.get("yellow").is_none());
+    }
+
+    #[test]
+    fn test_hand_normalized() {
+        let mut palette = Palette::new();
+        let hand = Hand::<hand::Unnormalized>::parse_with("3 red, 4 cyan, 2 red", &mut palette)
+            .unwrap()
+            .1
+            .normalized();
+        assert_eq!(hand::Normalized(vec![5, 4]), hand.0);
+        assert_eq!(
+            Hand::<hand::Normalized>::from(Hand::new_normalized(4, 0, 3)),
+            Hand::<hand::Unnormalized>::parse_with("3 blue, 4 red", &mut Palette::rgb())
+                .unwrap()
+                .1
+                .normalized(),
+        );
+    }
+}
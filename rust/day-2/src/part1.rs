@@ -1,6 +1,17 @@
-use crate::game::{hand, Game, Hand};
+use crate::{
+    bag::Bag,
+    game::{hand, Game},
+    scan,
+};
+
+/// The bag from the puzzle: 12 red cubes, 13 green cubes, and 14 blue cubes.
+pub const PUZZLE_BAG: Bag = Bag::new(12, 13, 14);
 
 pub fn process(input: &str) -> usize {
+    process_with_bag(input, &PUZZLE_BAG)
+}
+
+pub fn process_with_bag(input: &str, bag: &Bag) -> usize {
     input
         .lines()
         .map(|line| {
@@ -8,21 +19,14 @@ pub fn process(input: &str) -> usize {
                 .expect("invalid input")
                 .1
         })
-        .filter(is_valid_game)
+        .filter(|game| bag.allows(game))
         .map(|game| game.id)
         .sum()
 }
 
-fn is_valid_game(game: &Game<hand::Normalized>) -> bool {
-    const RED_CUBES: usize = 12;
-    const GREEN_CUBES: usize = 13;
-    const BLUE_CUBES: usize = 14;
-
-    game.hands.iter().all(|Hand(hand)| {
-        hand.red_amount <= RED_CUBES
-            && hand.green_amount <= GREEN_CUBES
-            && hand.blue_amount <= BLUE_CUBES
-    })
+/// Same as [`process`], but without allocating.
+pub fn process_bytes(input: &[u8]) -> usize {
+    scan::part1(input, &PUZZLE_BAG).expect("invalid input")
 }
 
 #[cfg(test)]
@@ -37,5 +41,8 @@ Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
 Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
 Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
         assert_eq!(8, process(input));
+        assert_eq!(8, process_bytes(input.as_bytes()));
+        assert_eq!(15, process_with_bag(input, &Bag::new(20, 13, 15)));
+        assert_eq!(0, process_with_bag(input, &Bag::default()));
     }
 }
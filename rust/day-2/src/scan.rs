@@ -0,0 +1,199 @@
+//! Allocation-free parser over the raw input bytes.
+//!
+//! Instead of building [`Game`](crate::game::Game)s, every game is folded into its minimum bag
+//! while it is being read, which is all both parts need.
+
+use crate::{bag::Bag, game::Color};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    #[error("unexpected byte {byte:?} at offset {offset}")]
+    UnexpectedByte { offset: usize, byte: char },
+    #[error("unexpected end of input")]
+    UnexpectedEnd,
+    #[error("number at offset {offset} is too large")]
+    Overflow { offset: usize },
+}
+
+/// Iterator over the games of the input as pairs of game ID and minimum bag.
+pub struct Games<'a> {
+    input: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Games<'a> {
+    pub fn new(input: &'a [u8]) -> Self {
+        Self { input, offset: 0 }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.offset).copied()
+    }
+
+    fn expect(&mut self, expected: &[u8]) -> Result<(), ParseError> {
+        for &byte in expected {
+            match self.peek() {
+                Some(b) if b == byte => self.offset += 1,
+                Some(b) => {
+                    return Err(ParseError::UnexpectedByte {
+                        offset: self.offset,
+                        byte: b as char,
+                    })
+                }
+                None => return Err(ParseError::UnexpectedEnd),
+            }
+        }
+        Ok(())
+    }
+
+    fn number(&mut self) -> Result<usize, ParseError> {
+        let start = self.offset;
+        let mut value = 0usize;
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add((digit - b'0') as usize))
+                .ok_or(ParseError::Overflow { offset: start })?;
+            self.offset += 1;
+        }
+        if self.offset == start {
+            return Err(self.unexpected());
+        }
+        Ok(value)
+    }
+
+    fn color(&mut self) -> Result<Color, ParseError> {
+        let color = match self.peek() {
+            Some(b'r') => Color::Red,
+            Some(b'g') => Color::Green,
+            Some(b'b') => Color::Blue,
+            _ => return Err(self.unexpected()),
+        };
+        self.expect(color.name().as_bytes())?;
+        Ok(color)
+    }
+
+    fn unexpected(&self) -> ParseError {
+        match self.peek() {
+            Some(byte) => ParseError::UnexpectedByte {
+                offset: self.offset,
+                byte: byte as char,
+            },
+            None => ParseError::UnexpectedEnd,
+        }
+    }
+
+    fn game(&mut self) -> Result<(usize, Bag), ParseError> {
+        self.expect(b"Game ")?;
+        let id = self.number()?;
+        self.expect(b": ")?;
+
+        let mut minimum = Bag::default();
+        let mut hand = Bag::default();
+        loop {
+            let amount = self.number()?;
+            self.expect(b" ")?;
+            match self.color()? {
+                Color::Red => hand.red += amount,
+                Color::Green => hand.green += amount,
+                Color::Blue => hand.blue += amount,
+            }
+
+            match self.peek() {
+                Some(b',') => self.expect(b", ")?,
+                Some(b';') => {
+                    self.expect(b"; ")?;
+                    minimum = minimum.max(std::mem::take(&mut hand));
+                }
+                Some(b'\r') => {
+                    self.expect(b"\r\n")?;
+                    break;
+                }
+                Some(b'\n') => {
+                    self.offset += 1;
+                    break;
+                }
+                None => break,
+                Some(_) => return Err(self.unexpected()),
+            }
+        }
+        Ok((id, minimum.max(hand)))
+    }
+}
+
+impl<'a> Iterator for Games<'a> {
+    type Item = Result<(usize, Bag), ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.input.len() {
+            return None;
+        }
+        let game = self.game();
+        if game.is_err() {
+            // stop at the first error
+            self.offset = self.input.len();
+        }
+        Some(game)
+    }
+}
+
+/// Part 1 without allocating: the sum of IDs of the games possible with the `bag`.
+pub fn part1(input: &[u8], bag: &Bag) -> Result<usize, ParseError> {
+    Games::new(input).try_fold(0, |sum, game| {
+        let (id, minimum) = game?;
+        Ok(if bag.holds(&minimum) { sum + id } else { sum })
+    })
+}
+
+/// Part 2 without allocating: the sum of powers of the minimum bags of all games.
+pub fn part2(input: &[u8]) -> Result<usize, ParseError> {
+    Games::new(input).try_fold(0, |sum, game| Ok(sum + game?.1.power()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    use crate::part1::PUZZLE_BAG;
+
+    const INPUT: &str = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+    #[test]
+    fn test_games() {
+        assert_eq!(
+            vec![
+                Ok((1, Bag::new(4, 2, 6))),
+                Ok((2, Bag::new(1, 3, 4))),
+                Ok((3, Bag::new(20, 13, 6))),
+                Ok((4, Bag::new(14, 3, 15))),
+                Ok((5, Bag::new(6, 3, 2))),
+            ],
+            Games::new(INPUT.as_bytes()).collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case(INPUT)]
+    #[case(&format!("{INPUT}\n"))]
+    #[case(&INPUT.replace('\n', "\r\n"))]
+    fn test_parts(#[case] input: &str) {
+        assert_eq!(Ok(8), part1(input.as_bytes(), &PUZZLE_BAG));
+        assert_eq!(Ok(2286), part2(input.as_bytes()));
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 purple", ParseError::UnexpectedByte { offset: 10, byte: 'p' })]
+    #[case("Game 1: 3 bleu", ParseError::UnexpectedByte { offset: 12, byte: 'e' })]
+    #[case("Game 1: 3 red;4 blue", ParseError::UnexpectedByte { offset: 14, byte: '4' })]
+    #[case("Game 1: 3 red,", ParseError::UnexpectedEnd)]
+    #[case("Game 1: 99999999999999999999 red", ParseError::Overflow { offset: 8 })]
+    fn test_parse_error(#[case] input: &str, #[case] expected: ParseError) {
+        assert_eq!(Err(expected), part2(input.as_bytes()));
+    }
+}
@@ -0,0 +1,91 @@
+use std::{collections::BTreeMap, fmt};
+
+use crate::{
+    bag::Bag,
+    game::{hand, Game},
+};
+
+/// Summary statistics over the games of a whole input.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub games: usize,
+    /// The greatest amount of cubes of each color shown in any hand of any game.
+    pub max_per_color: Bag,
+    /// Number of games by the number of hands in them.
+    pub hands_per_game: BTreeMap<usize, usize>,
+    /// The game needing the bag of the greatest power, with that bag.
+    /// On a tie the first such game wins.
+    pub most_constrained: Option<(usize, Bag)>,
+}
+
+impl Stats {
+    pub fn from_games<'a>(games: impl IntoIterator<Item = &'a Game<hand::Normalized>>) -> Self {
+        games.into_iter().fold(Self::default(), |mut stats, game| {
+            let bag = game.minimum_bag();
+            stats.games += 1;
+            stats.max_per_color = stats.max_per_color.max(bag);
+            *stats.hands_per_game.entry(game.hands.len()).or_default() += 1;
+            if stats
+                .most_constrained
+                .is_none_or(|(_, most)| bag.power() > most.power())
+            {
+                stats.most_constrained = Some((game.id, bag));
+            }
+            stats
+        })
+    }
+
+    pub fn from_input(input: &str) -> Self {
+        let games: Vec<_> = input
+            .lines()
+            .map(|line| {
+                Game::<hand::Normalized>::parse(line)
+                    .expect("invalid input")
+                    .1
+            })
+            .collect();
+        Self::from_games(&games)
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Games: {}", self.games)?;
+        writeln!(f, "Max per color: {}", self.max_per_color)?;
+        writeln!(f, "Hands per game:")?;
+        for (hands, games) in &self.hands_per_game {
+            writeln!(f, "  {hands}: {games} game(s)")?;
+        }
+        match self.most_constrained {
+            Some((id, bag)) => writeln!(
+                f,
+                "Most constrained game: Game {id} (minimum bag {bag}, power {})",
+                bag.power()
+            ),
+            None => writeln!(f, "Most constrained game: none"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_input() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+        assert_eq!(
+            Stats {
+                games: 5,
+                max_per_color: Bag::new(20, 13, 15),
+                hands_per_game: BTreeMap::from([(2, 1), (3, 4)]),
+                most_constrained: Some((3, Bag::new(20, 13, 6))),
+            },
+            Stats::from_input(input)
+        );
+    }
+}
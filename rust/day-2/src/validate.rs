@@ -0,0 +1,441 @@
+//! Semantic validation of the input, beyond what the parser accepts.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use nom::{bytes::complete::tag, sequence::delimited};
+
+use crate::game::{usize, Color, Cubes};
+
+/// How harshly anomalies in the input are reported.
+///
+/// Malformed lines are errors under every strictness.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Strictness {
+    /// Every anomaly is a warning.
+    Lenient,
+    /// Anomalies that make game IDs ambiguous are errors, the rest are warnings.
+    #[default]
+    Normal,
+    /// Every anomaly is an error.
+    Strict,
+}
+
+impl Strictness {
+    fn severity(self, anomaly: &Anomaly) -> Severity {
+        match (self, anomaly) {
+            (_, Anomaly::Syntax { .. }) | (Self::Strict, _) => Severity::Error,
+            (Self::Normal, Anomaly::DuplicateGameId { .. }) => Severity::Error,
+            _ => Severity::Warning,
+        }
+    }
+}
+
+impl FromStr for Strictness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "lenient" => Ok(Self::Lenient),
+            "normal" => Ok(Self::Normal),
+            "strict" => Ok(Self::Strict),
+            _ => Err(format!(
+                "unknown strictness {s:?}, expected lenient, normal or strict"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Warning => f.write_str("warning"),
+            Self::Error => f.write_str("error"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Anomaly {
+    #[error("expected {expected}")]
+    Syntax { expected: &'static str },
+    #[error("{color} appears more than once in the hand, first at column {first_column}")]
+    DuplicateColor { color: Color, first_column: usize },
+    #[error("game {id} was already defined at line {first_line}")]
+    DuplicateGameId { id: usize, first_line: usize },
+    #[error("games {} to {} are missing", .from, .to)]
+    MissingGameIds { from: usize, to: usize },
+    #[error("game {id} comes after game {previous}")]
+    OutOfOrderGameId { id: usize, previous: usize },
+    #[error("zero {color} cubes")]
+    ZeroAmount { color: Color },
+    #[error("the hand is empty")]
+    EmptyHand,
+}
+
+/// One-based position in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub location: Location,
+    pub severity: Severity,
+    pub anomaly: Anomaly,
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}: {}", self.location, self.severity, self.anomaly)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Reports anomalies the parser either accepts silently or rejects without a location:
+/// malformed lines, repeated colors within a hand, duplicated, missing or out-of-order
+/// game IDs, zero amounts and empty hands.
+pub fn validate(input: &str, strictness: Strictness) -> Report {
+    let mut validator = Validator {
+        strictness,
+        report: Report::default(),
+        first_lines: HashMap::new(),
+        last_id: None,
+    };
+    for (i, line) in input.lines().enumerate() {
+        validator.line(i + 1, line);
+    }
+    validator.finish()
+}
+
+struct Validator {
+    strictness: Strictness,
+    report: Report,
+    /// Line of the first definition of every game ID.
+    first_lines: HashMap<usize, usize>,
+    /// ID of the latest game that was not a duplicate.
+    last_id: Option<usize>,
+}
+
+impl Validator {
+    fn push(&mut self, line: usize, column: usize, anomaly: Anomaly) {
+        self.report.diagnostics.push(Diagnostic {
+            location: Location { line, column },
+            severity: self.strictness.severity(&anomaly),
+            anomaly,
+        });
+    }
+
+    fn line(&mut self, line_number: usize, line: &str) {
+        let Ok((hands, id)) = delimited(tag("Game "), usize, tag(": "))(line) else {
+            self.push(
+                line_number,
+                1,
+                Anomaly::Syntax {
+                    expected: "`Game <id>: `",
+                },
+            );
+            return;
+        };
+
+        if let Some(&first_line) = self.first_lines.get(&id) {
+            self.push(line_number, 6, Anomaly::DuplicateGameId { id, first_line });
+        } else {
+            self.first_lines.insert(id, line_number);
+            if let Some(previous) = self.last_id.filter(|&previous| previous > id) {
+                self.push(line_number, 6, Anomaly::OutOfOrderGameId { id, previous });
+            }
+            self.last_id = Some(id);
+        }
+
+        // the separators are exactly the ones of `Game::parse`, anything else ends up
+        // in a cube that does not parse
+        let mut offset = line.len() - hands.len();
+        for hand in hands.split("; ") {
+            self.hand(line_number, offset, hand);
+            offset += hand.len() + 2;
+        }
+    }
+
+    /// Reports the gaps in the game IDs, which are only known once every line is read,
+    /// at the first game after each gap.
+    fn finish(mut self) -> Report {
+        let mut first_lines: Vec<_> = self
+            .first_lines
+            .iter()
+            .map(|(&id, &line)| (id, line))
+            .collect();
+        first_lines.sort_unstable();
+        let mut next_id = 1;
+        for (id, line_number) in first_lines {
+            if id > next_id {
+                let anomaly = Anomaly::MissingGameIds {
+                    from: next_id,
+                    to: id - 1,
+                };
+                self.push(line_number, 6, anomaly);
+            }
+            next_id = id + 1;
+        }
+        self.report.diagnostics.sort_by_key(|d| d.location);
+        self.report
+    }
+
+    /// Validates the `hand` starting at the byte `offset` of the line.
+    fn hand(&mut self, line_number: usize, offset: usize, hand: &str) {
+        if hand.is_empty() {
+            self.push(line_number, offset + 1, Anomaly::EmptyHand);
+            return;
+        }
+
+        let mut first_columns: [Option<usize>; 3] = [None; 3];
+        let mut offset = offset;
+        for cubes in hand.split(", ") {
+            let column = offset + 1;
+            offset += cubes.len() + 2;
+
+            let Ok((rest, Cubes { amount, color })) = Cubes::parse(cubes) else {
+                self.push(
+                    line_number,
+                    column,
+                    Anomaly::Syntax {
+                        expected: "`<amount> <color>`",
+                    },
+                );
+                continue;
+            };
+            if !rest.is_empty() {
+                self.push(
+                    line_number,
+                    column + cubes.len() - rest.len(),
+                    Anomaly::Syntax {
+                        expected: "`, ` or `; `",
+                    },
+                );
+            }
+            if amount == 0 {
+                self.push(line_number, column, Anomaly::ZeroAmount { color });
+            }
+            match &mut first_columns[color as usize] {
+                Some(first_column) => {
+                    let anomaly = Anomaly::DuplicateColor {
+                        color,
+                        first_column: *first_column,
+                    };
+                    self.push(line_number, column, anomaly);
+                }
+                first_column => *first_column = Some(column),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[test]
+    fn test_validate_clean_input() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red";
+        assert_eq!(Report::default(), validate(input, Strictness::Strict));
+    }
+
+    #[test]
+    fn test_validate_anomalies() {
+        let input = "Game 1: 3 red, 4 red; 2 green
+Game 3: 0 blue; ; 1 red
+Game 1: 2 green
+Game 4: 2 purple";
+        let report = validate(input, Strictness::Normal);
+        let diagnostics: Vec<_> = report
+            .diagnostics
+            .iter()
+            .map(|d| (d.location.line, d.location.column, d.severity, &d.anomaly))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    1,
+                    16,
+                    Severity::Warning,
+                    &Anomaly::DuplicateColor {
+                        color: Color::Red,
+                        first_column: 9
+                    }
+                ),
+                (
+                    2,
+                    6,
+                    Severity::Warning,
+                    &Anomaly::MissingGameIds { from: 2, to: 2 }
+                ),
+                (
+                    2,
+                    9,
+                    Severity::Warning,
+                    &Anomaly::ZeroAmount { color: Color::Blue }
+                ),
+                (2, 17, Severity::Warning, &Anomaly::EmptyHand),
+                (
+                    3,
+                    6,
+                    Severity::Error,
+                    &Anomaly::DuplicateGameId {
+                        id: 1,
+                        first_line: 1
+                    }
+                ),
+                (
+                    4,
+                    9,
+                    Severity::Error,
+                    &Anomaly::Syntax {
+                        expected: "`<amount> <color>`"
+                    }
+                ),
+            ],
+            diagnostics
+        );
+        assert!(report.has_errors());
+        assert_eq!(
+            "1:16: warning: red appears more than once in the hand, first at column 9",
+            report.diagnostics[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_out_of_order_ids() {
+        let report = validate(
+            "Game 1: 1 red\nGame 3: 1 red\nGame 2: 1 red",
+            Strictness::Normal,
+        );
+        assert_eq!(
+            vec![Diagnostic {
+                location: Location { line: 3, column: 6 },
+                severity: Severity::Warning,
+                anomaly: Anomaly::OutOfOrderGameId { id: 2, previous: 3 },
+            }],
+            report.diagnostics
+        );
+
+        let input = "Game 1: 1 red
+Game 3: 1 red
+Game 2: 1 red
+Game 6: 1 red
+Game 4: 1 red";
+        let report = validate(input, Strictness::Normal);
+        assert_eq!(
+            vec![
+                (
+                    Location { line: 3, column: 6 },
+                    Anomaly::OutOfOrderGameId { id: 2, previous: 3 }
+                ),
+                (
+                    Location { line: 4, column: 6 },
+                    Anomaly::MissingGameIds { from: 5, to: 5 }
+                ),
+                (
+                    Location { line: 5, column: 6 },
+                    Anomaly::OutOfOrderGameId { id: 4, previous: 6 }
+                ),
+            ],
+            report
+                .diagnostics
+                .iter()
+                .map(|d| (d.location, d.anomaly.clone()))
+                .collect::<Vec<_>>()
+        );
+        assert!(!report.has_errors());
+    }
+
+    #[rstest]
+    #[case(Strictness::Lenient, Severity::Warning)]
+    #[case(Strictness::Normal, Severity::Warning)]
+    #[case(Strictness::Strict, Severity::Error)]
+    fn test_strictness(#[case] strictness: Strictness, #[case] expected: Severity) {
+        let report = validate("Game 1: 0 red", strictness);
+        assert_eq!(expected, report.diagnostics[0].severity);
+    }
+
+    #[rstest]
+    #[case("Game 1: 3 red,4 blue", 14, "`, ` or `; `")]
+    #[case("Game 1: 3 red;4 blue", 14, "`, ` or `; `")]
+    #[case("Game 1: 3 red, 4 blue;", 22, "`, ` or `; `")]
+    #[case("Game 1:3 red", 1, "`Game <id>: `")]
+    #[case("Game 1:  3 red", 9, "`<amount> <color>`")]
+    fn test_separators(#[case] line: &str, #[case] column: usize, #[case] expected: &'static str) {
+        let report = validate(line, Strictness::Strict);
+        assert_eq!(
+            vec![Diagnostic {
+                location: Location { line: 1, column },
+                severity: Severity::Error,
+                anomaly: Anomaly::Syntax { expected },
+            }],
+            report.diagnostics,
+            "{line}"
+        );
+    }
+
+    #[test]
+    fn test_malformed_header() {
+        let report = validate("Gaem 1: 3 red", Strictness::Lenient);
+        assert_eq!(
+            vec![Diagnostic {
+                location: Location { line: 1, column: 1 },
+                severity: Severity::Error,
+                anomaly: Anomaly::Syntax {
+                    expected: "`Game <id>: `"
+                },
+            }],
+            report.diagnostics
+        );
+    }
+}
@@ -0,0 +1,27 @@
+//! Run with `cargo test -p day-2 --features dhat-heap --test alloc_budget`.
+#![cfg(feature = "dhat-heap")]
+
+use common::alloc_budget::{check, Budget};
+use day_2::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &str = include_str!("../input.txt");
+
+const PART1: Budget<usize> = Budget {
+    answer: 2149,
+    total_blocks: 585,
+    max_bytes: 576,
+};
+const PART2: Budget<usize> = Budget {
+    answer: 71274,
+    total_blocks: 585,
+    max_bytes: 576,
+};
+
+#[test]
+fn process_stays_within_budget() {
+    check("part1", INPUT, PART1, part1::process);
+    check("part2", INPUT, PART2, part2::process);
+}
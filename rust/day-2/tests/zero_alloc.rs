@@ -0,0 +1,27 @@
+//! Run with `cargo test -p day-2 --features dhat-heap --test zero_alloc`.
+#![cfg(feature = "dhat-heap")]
+
+use day_2::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &[u8] = b"Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green";
+
+#[test]
+fn process_bytes_does_not_allocate() {
+    let _profiler = dhat::Profiler::builder().testing().build();
+
+    let before = dhat::HeapStats::get();
+    let part1 = part1::process_bytes(INPUT);
+    let part2 = part2::process_bytes(INPUT);
+    let after = dhat::HeapStats::get();
+
+    assert_eq!(8, part1);
+    assert_eq!(2286, part2);
+    dhat::assert_eq!(before.total_blocks, after.total_blocks);
+}
@@ -0,0 +1,137 @@
+use std::{fmt, ops::RangeInclusive, path::PathBuf, process::ExitCode, str::FromStr};
+
+use clap::{Parser, ValueEnum};
+use day_3::{
+    index::{Gear, Index},
+    schematic::{self, Padding},
+};
+
+/// Reduces the numbers around the symbols of the schematic.
+///
+/// E.g. `query --symbol '*' --arity 3 --reduce product` sums the products of the numbers
+/// around every `*` adjacent to exactly 3 numbers, and `query --arity 0 --reduce count --list`
+/// lists the symbols adjacent to no numbers.
+#[derive(Debug, Parser)]
+struct Args {
+    /// Symbols to consider; every symbol is considered if omitted
+    #[arg(long = "symbol")]
+    symbols: Vec<char>,
+    /// Amount of adjacent numbers, as `N`, `N..`, `..=M` or `N..=M`
+    #[arg(long, default_value = "0..")]
+    arity: Arity,
+    #[arg(long, value_enum, default_value_t = Reduce::Sum)]
+    reduce: Reduce,
+    /// Print every matching symbol along with its numbers
+    #[arg(long)]
+    list: bool,
+    /// Pad rows shorter than the longest one with `.` instead of rejecting them
+    #[arg(long)]
+    pad: bool,
+    /// Puzzle input to read instead of the bundled one
+    #[arg(long)]
+    input: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Reduce {
+    Sum,
+    Product,
+    Count,
+    Min,
+    Max,
+}
+
+impl Reduce {
+    fn apply(self, gear: &Gear) -> u64 {
+        let values = gear.values().map(u64::from);
+        match self {
+            Self::Sum => values.sum(),
+            Self::Product => values.product(),
+            Self::Count => values.len() as u64,
+            Self::Min => values.min().unwrap_or(0),
+            Self::Max => values.max().unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Arity(RangeInclusive<usize>);
+
+impl FromStr for Arity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let number = |s: &str| {
+            s.parse::<usize>()
+                .map_err(|e| format!("invalid arity {s:?}: {e}"))
+        };
+        let range = match s.split_once("..") {
+            None => number(s).map(|n| n..=n)?,
+            Some((start, end)) => {
+                let start = if start.is_empty() { 0 } else { number(start)? };
+                let end = match end.strip_prefix('=') {
+                    Some(end) => number(end)?,
+                    None if end.is_empty() => usize::MAX,
+                    None => {
+                        return Err(format!(
+                            "invalid arity {s:?}: only `..=` ranges are supported"
+                        ))
+                    }
+                };
+                start..=end
+            }
+        };
+        Ok(Self(range))
+    }
+}
+
+impl fmt::Display for Arity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..={}", self.0.start(), self.0.end())
+    }
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let file = match &args.input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => include_str!("../../input.txt").to_owned(),
+    };
+    let padding = if args.pad {
+        Padding::Dots
+    } else {
+        Padding::Reject
+    };
+    let index = Index::new(&schematic::parse(&file, padding)?);
+
+    let results = index.gears(
+        |symbol| args.symbols.is_empty() || args.symbols.contains(&symbol.ch),
+        |arity| args.arity.0.contains(&arity),
+        |gear| {
+            let result = args.reduce.apply(&gear);
+            if args.list {
+                let (x, y) = gear.symbol.pos;
+                let values: Vec<_> = gear.values().map(|value| value.to_string()).collect();
+                println!(
+                    "{} at ({x}, {y}): [{}] -> {result}",
+                    gear.symbol.ch,
+                    values.join(", ")
+                );
+            }
+            result
+        },
+    );
+    let (count, total) = results.fold((0, 0), |(count, total), result| (count + 1, total + result));
+    println!("{count} symbol(s) matched, total: {total}");
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::{path::PathBuf, process::ExitCode};
+
+use clap::{Parser, ValueEnum};
+use day_3::{
+    index::Index,
+    render,
+    schematic::{self, Padding},
+};
+
+/// Renders the schematic with part numbers, other numbers and gears told apart.
+#[derive(Debug, Parser)]
+struct Args {
+    #[arg(long, value_enum, default_value_t = Format::Ansi)]
+    format: Format,
+    /// Pad rows shorter than the longest one with `.` instead of rejecting them
+    #[arg(long)]
+    pad: bool,
+    /// Puzzle input to read instead of the bundled one
+    #[arg(long)]
+    input: Option<PathBuf>,
+    /// File to write to instead of stdout
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Format {
+    Ansi,
+    Svg,
+    Html,
+}
+
+fn main() -> ExitCode {
+    match run(Args::parse()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let file = match &args.input {
+        Some(path) => std::fs::read_to_string(path)?,
+        None => include_str!("../../input.txt").to_owned(),
+    };
+    let padding = if args.pad {
+        Padding::Dots
+    } else {
+        Padding::Reject
+    };
+    let schematic = schematic::parse(&file, padding)?;
+    let index = Index::new(&schematic);
+
+    let rendered = match args.format {
+        Format::Ansi => render::ansi(&schematic, &index),
+        Format::Svg => render::svg(&schematic, &index),
+        Format::Html => render::html(&schematic, &index),
+    };
+    match &args.output {
+        Some(path) => std::fs::write(path, rendered)?,
+        None => print!("{rendered}"),
+    }
+    Ok(())
+}
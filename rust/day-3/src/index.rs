@@ -0,0 +1,253 @@
+use common::grid::{Grid, Pos};
+
+use crate::schematic::{self, is_symbol, numbers, Number, Padding, SchematicError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symbol {
+    pub pos: Pos,
+    pub ch: char,
+}
+
+/// Every number and symbol of an engine schematic along with the adjacency between them,
+/// built in a single pass over the schematic.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Index {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    /// Indices of the numbers adjacent to every symbol, delimited by `symbol_offsets`.
+    symbol_numbers: Vec<usize>,
+    symbol_offsets: Vec<usize>,
+    /// Indices of the symbols adjacent to every number, delimited by `number_offsets`.
+    number_symbols: Vec<usize>,
+    number_offsets: Vec<usize>,
+}
+
+impl Index {
+    pub fn new(schematic: &Grid<u8>) -> Self {
+        let numbers: Vec<Number> = numbers(schematic).collect();
+        let mut labels = Grid::from_fn(schematic.width(), schematic.height(), |_| None);
+        for (i, number) in numbers.iter().enumerate() {
+            let (x, y) = number.pos;
+            for x in x..x + number.len {
+                labels[(x, y)] = Some(i);
+            }
+        }
+
+        let mut symbols = Vec::new();
+        let mut symbol_numbers = Vec::new();
+        let mut symbol_offsets = vec![0];
+        let mut adjacent_symbols = vec![0; numbers.len()];
+        for (pos, &cell) in schematic.iter().filter(|(_, &cell)| is_symbol(cell)) {
+            let start = symbol_numbers.len();
+            for i in schematic.neighbours8(pos).filter_map(|pos| labels[pos]) {
+                if !symbol_numbers[start..].contains(&i) {
+                    symbol_numbers.push(i);
+                    adjacent_symbols[i] += 1;
+                }
+            }
+            symbol_offsets.push(symbol_numbers.len());
+            symbols.push(Symbol {
+                pos,
+                ch: cell as char,
+            });
+        }
+
+        // transpose the adjacency with a counting sort by number
+        let mut number_offsets = Vec::with_capacity(numbers.len() + 1);
+        number_offsets.push(0);
+        for count in adjacent_symbols {
+            number_offsets.push(number_offsets.last().unwrap() + count);
+        }
+        let mut number_symbols = vec![0; symbol_numbers.len()];
+        let mut next = number_offsets.clone();
+        for (symbol, bounds) in symbol_offsets.windows(2).enumerate() {
+            for &number in &symbol_numbers[bounds[0]..bounds[1]] {
+                number_symbols[next[number]] = symbol;
+                next[number] += 1;
+            }
+        }
+
+        Self {
+            numbers,
+            symbols,
+            symbol_numbers,
+            symbol_offsets,
+            number_symbols,
+            number_offsets,
+        }
+    }
+
+    pub fn parse(input: &str) -> Result<Self, SchematicError> {
+        schematic::parse(input, Padding::Reject).map(|schematic| Self::new(&schematic))
+    }
+
+    pub fn numbers(&self) -> &[Number] {
+        &self.numbers
+    }
+
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Indices of the numbers adjacent to the symbol with the index `symbol`.
+    pub fn numbers_around(&self, symbol: usize) -> &[usize] {
+        &self.symbol_numbers[self.symbol_offsets[symbol]..self.symbol_offsets[symbol + 1]]
+    }
+
+    /// Indices of the symbols adjacent to the number with the index `number`.
+    pub fn symbols_around(&self, number: usize) -> &[usize] {
+        &self.number_symbols[self.number_offsets[number]..self.number_offsets[number + 1]]
+    }
+
+    /// Numbers adjacent to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| !self.symbols_around(i).is_empty())
+            .map(|(_, number)| number)
+    }
+
+    /// Reduces the numbers around every symbol that matches the `symbol_filter`
+    /// and has an amount of adjacent numbers that matches the `arity_filter`.
+    pub fn gears<'a, R>(
+        &'a self,
+        symbol_filter: impl Fn(&Symbol) -> bool + 'a,
+        arity_filter: impl Fn(usize) -> bool + 'a,
+        reducer: impl Fn(Gear<'a>) -> R + 'a,
+    ) -> impl Iterator<Item = R> + 'a {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(move |(_, symbol)| symbol_filter(symbol))
+            .map(|(i, symbol)| Gear {
+                symbol,
+                numbers: self.numbers_around(i),
+                index: self,
+            })
+            .filter(move |gear| arity_filter(gear.numbers.len()))
+            .map(reducer)
+    }
+
+    /// Products of the two numbers around every `*` adjacent to exactly two numbers.
+    pub fn gear_ratios(&self) -> impl Iterator<Item = u64> + '_ {
+        self.gears(
+            |symbol| symbol.ch == '*',
+            |arity| arity == 2,
+            |gear| gear.values().map(u64::from).product(),
+        )
+    }
+}
+
+/// A symbol along with the numbers adjacent to it.
+#[derive(Debug, Clone, Copy)]
+pub struct Gear<'a> {
+    pub symbol: &'a Symbol,
+    numbers: &'a [usize],
+    index: &'a Index,
+}
+
+impl<'a> Gear<'a> {
+    pub fn numbers(&self) -> impl ExactSizeIterator<Item = &'a Number> + 'a {
+        let index = self.index;
+        self.numbers.iter().map(move |&i| &index.numbers[i])
+    }
+
+    pub fn values(&self) -> impl ExactSizeIterator<Item = u32> + 'a {
+        self.numbers().map(|number| number.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_index() {
+        let index = Index::parse(INPUT).unwrap();
+        assert_eq!(10, index.numbers().len());
+        assert_eq!(
+            vec![
+                Symbol {
+                    pos: (3, 1),
+                    ch: '*'
+                },
+                Symbol {
+                    pos: (6, 3),
+                    ch: '#'
+                },
+                Symbol {
+                    pos: (3, 4),
+                    ch: '*'
+                },
+                Symbol {
+                    pos: (5, 5),
+                    ch: '+'
+                },
+                Symbol {
+                    pos: (3, 8),
+                    ch: '$'
+                },
+                Symbol {
+                    pos: (5, 8),
+                    ch: '*'
+                },
+            ],
+            index.symbols()
+        );
+
+        let values = |numbers: &[usize]| -> Vec<u32> {
+            numbers.iter().map(|&i| index.numbers()[i].value).collect()
+        };
+        assert_eq!(vec![467, 35], values(index.numbers_around(0)));
+        assert_eq!(vec![617], values(index.numbers_around(2)));
+        assert_eq!(vec![755, 598], values(index.numbers_around(5)));
+
+        let number_114 = index.numbers().iter().position(|n| n.value == 114).unwrap();
+        assert!(index.symbols_around(number_114).is_empty());
+        let number_35 = index.numbers().iter().position(|n| n.value == 35).unwrap();
+        assert_eq!(&[0], index.symbols_around(number_35));
+    }
+
+    #[test]
+    fn test_queries() {
+        let index = Index::parse(INPUT).unwrap();
+        assert_eq!(4361, index.part_numbers().map(|n| n.value).sum::<u32>());
+        assert_eq!(vec![16345, 451490], index.gear_ratios().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_gears() {
+        let index = Index::parse(INPUT).unwrap();
+        assert_eq!(
+            vec![592],
+            index
+                .gears(|s| s.ch == '+', |_| true, |gear| gear.values().sum::<u32>())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(3, 4)],
+            index
+                .gears(|_| true, |arity| arity == 1, |gear| gear.symbol.pos)
+                .filter(|&pos| index.symbols().iter().any(|s| s.pos == pos && s.ch == '*'))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            0,
+            index
+                .gears(|_| true, |arity| arity == 0, |gear| gear.symbol.pos)
+                .count()
+        );
+    }
+}
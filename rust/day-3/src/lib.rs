@@ -1,2 +1,6 @@
+pub mod index;
+pub mod live;
 pub mod part1;
 pub mod part2;
+pub mod render;
+pub mod schematic;
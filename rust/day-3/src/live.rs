@@ -0,0 +1,258 @@
+use common::grid::{Grid, Pos};
+
+use crate::{
+    index::Index,
+    schematic::{
+        self, check_number_at, check_numbers, is_symbol, number_at, Number, Padding, SchematicError,
+    },
+};
+
+/// An editable schematic that keeps the sum of the part numbers and the sum of the gear ratios
+/// up to date, re-examining only the numbers and symbols around every edited cell.
+///
+/// Every number fits in a `u32`, the sum of the gear ratios is 128-bit, as a single gear of
+/// two such numbers already takes up to 64 bits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schematic {
+    grid: Grid<u8>,
+    part_sum: u64,
+    gear_sum: u128,
+}
+
+impl Schematic {
+    /// Fails if a number of the `grid` does not fit in a `u32`.
+    pub fn new(grid: Grid<u8>) -> Result<Self, SchematicError> {
+        check_numbers(&grid)?;
+        let index = Index::new(&grid);
+        let part_sum = index.part_numbers().map(|n| u64::from(n.value)).sum();
+        let gear_sum = index
+            .gears(
+                |symbol| symbol.ch == '*',
+                |arity| arity == 2,
+                |gear| gear.values().map(u128::from).product::<u128>(),
+            )
+            .sum();
+        Ok(Self {
+            grid,
+            part_sum,
+            gear_sum,
+        })
+    }
+
+    pub fn parse(input: &str) -> Result<Self, SchematicError> {
+        schematic::parse(input, Padding::Reject).and_then(Self::new)
+    }
+
+    pub fn grid(&self) -> &Grid<u8> {
+        &self.grid
+    }
+
+    pub fn part_sum(&self) -> u64 {
+        self.part_sum
+    }
+
+    pub fn gear_sum(&self) -> u128 {
+        self.gear_sum
+    }
+
+    /// Replaces the cell at `(x, y)` with `ch`, which must be a printable ASCII character
+    /// other than space. Fails, leaving the schematic as it was, if the edit would make
+    /// a number that does not fit in a `u32`.
+    ///
+    /// # Panics
+    ///
+    /// If `(x, y)` is outside the schematic.
+    pub fn set(&mut self, x: usize, y: usize, ch: char) -> Result<(), SchematicError> {
+        assert!(
+            self.grid.contains((x, y)),
+            "({x}, {y}) is outside the schematic"
+        );
+        let (row, column) = (y + 1, x + 1);
+        if !ch.is_ascii() {
+            return Err(SchematicError::NonAscii { row, column, ch });
+        }
+        if !ch.is_ascii_graphic() {
+            return Err(SchematicError::InvalidCharacter { row, column, ch });
+        }
+
+        let pos = (x, y);
+        let (old, new) = (self.grid[pos], ch as u8);
+        if old == new {
+            return Ok(());
+        }
+
+        // Only the numbers touching the cell, before or after the edit, can change their part
+        // status, and only the cell itself and the symbols around those numbers can change
+        // their gear ratio.
+        let before = numbers_near(&self.grid, pos);
+        self.grid[pos] = new;
+        if let Err(e) = check_number_at(&self.grid, pos) {
+            self.grid[pos] = old;
+            return Err(e);
+        }
+        let after = numbers_near(&self.grid, pos);
+        let mut symbols: Vec<Pos> = before
+            .iter()
+            .chain(&after)
+            .flat_map(|number| number.neighbours(&self.grid))
+            .chain([pos])
+            .collect();
+        symbols.sort_unstable();
+        symbols.dedup();
+
+        self.grid[pos] = old;
+        let (parts, gears) = self.contribution(&before, &symbols);
+        self.part_sum -= parts;
+        self.gear_sum -= gears;
+
+        self.grid[pos] = new;
+        let (parts, gears) = self.contribution(&after, &symbols);
+        self.part_sum += parts;
+        self.gear_sum += gears;
+        Ok(())
+    }
+
+    /// What the part `numbers` and the gears among the `symbols` add to the sums.
+    fn contribution(&self, numbers: &[Number], symbols: &[Pos]) -> (u64, u128) {
+        let parts = numbers
+            .iter()
+            .filter(|number| {
+                number
+                    .neighbours(&self.grid)
+                    .any(|pos| is_symbol(self.grid[pos]))
+            })
+            .map(|number| u64::from(number.value))
+            .sum();
+        let gears = symbols.iter().map(|&pos| gear_ratio(&self.grid, pos)).sum();
+        (parts, gears)
+    }
+}
+
+/// Distinct numbers with a digit at `pos` or next to it.
+fn numbers_near(grid: &Grid<u8>, pos: Pos) -> Vec<Number> {
+    let mut numbers: Vec<Number> = std::iter::once(pos)
+        .chain(grid.neighbours8(pos))
+        .filter_map(|pos| number_at(grid, pos))
+        .collect();
+    numbers.sort_unstable_by_key(|number| number.pos);
+    numbers.dedup();
+    numbers
+}
+
+fn gear_ratio(grid: &Grid<u8>, pos: Pos) -> u128 {
+    if grid[pos] != b'*' {
+        return 0;
+    }
+    let mut numbers: Vec<Number> = grid
+        .neighbours8(pos)
+        .filter_map(|pos| number_at(grid, pos))
+        .collect();
+    numbers.sort_unstable_by_key(|number| number.pos);
+    numbers.dedup();
+    match numbers[..] {
+        [a, b] => u128::from(a.value) * u128::from(b.value),
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+    #[test]
+    fn test_set() {
+        let mut schematic = Schematic::parse(INPUT).unwrap();
+        assert_eq!((4361, 467835), (schematic.part_sum(), schematic.gear_sum()));
+
+        // 114 becomes a part number
+        schematic.set(4, 1, '#').unwrap();
+        assert_eq!((4475, 467835), (schematic.part_sum(), schematic.gear_sum()));
+
+        // 467 and 35 stop being a gear, 467 stops being a part number
+        schematic.set(3, 1, '.').unwrap();
+        assert_eq!((4008, 451490), (schematic.part_sum(), schematic.gear_sum()));
+
+        // 617 and 592 become a gear through a new `*`
+        schematic.set(3, 4, '.').unwrap();
+        schematic.set(3, 5, '*').unwrap();
+        assert_eq!(
+            (4008, 451490 + 617 * 592),
+            (schematic.part_sum(), schematic.gear_sum())
+        );
+
+        // merging 58 and 755 into a single number next to `+`
+        schematic.set(7, 6, '1').unwrap();
+        assert_eq!(
+            Schematic::new(schematic.grid().clone()),
+            Ok(schematic.clone())
+        );
+
+        // growing 592 into a number too large for a `u32`
+        for x in [1, 5, 6, 7, 8, 9] {
+            schematic.set(x, 6, '9').unwrap();
+        }
+        let edited = schematic.clone();
+        assert_eq!(
+            Err(SchematicError::NumberTooLarge { row: 7, column: 1 }),
+            schematic.set(0, 6, '9')
+        );
+        assert_eq!(edited, schematic);
+        assert_eq!(b".959299999", schematic.grid().row(6));
+
+        assert_eq!(
+            Err(SchematicError::InvalidCharacter {
+                row: 1,
+                column: 2,
+                ch: ' '
+            }),
+            schematic.set(1, 0, ' ')
+        );
+    }
+
+    /// A schematic along with a sequence of edits inside it. The cells are mostly digits,
+    /// so that some runs of them are too long for a `u32`.
+    fn schematic_with_edits() -> impl Strategy<Value = (Grid<u8>, Vec<(Pos, char)>)> {
+        let cell =
+            || prop::sample::select(b"..0123456789012345678901234567890123456789*#+".to_vec());
+        (1..=24usize, 1..=8usize).prop_flat_map(move |(width, height)| {
+            let edit = ((0..width, 0..height), cell().prop_map(char::from));
+            (
+                prop::collection::vec(cell(), width * height)
+                    .prop_map(move |cells| Grid::new(width, height, cells)),
+                prop::collection::vec(edit, 0..64),
+            )
+        })
+    }
+
+    proptest! {
+        /// Fills an empty schematic with the cells of the `grid` row by row, then applies the
+        /// `edits`, checking every edit against a full recomputation.
+        #[test]
+        fn set_matches_full_recomputation((grid, edits) in schematic_with_edits()) {
+            let (width, height) = (grid.width(), grid.height());
+            let mut schematic = Schematic::new(Grid::new(width, height, vec![b'.'; width * height])).unwrap();
+            let fill = (0..height).flat_map(|y| (0..width).map(move |x| (x, y))).map(|pos| (pos, char::from(grid[pos])));
+            for ((x, y), ch) in fill.chain(edits) {
+                let mut edited = schematic.grid().clone();
+                edited[(x, y)] = ch as u8;
+                if let Err(e) = schematic.set(x, y, ch) {
+                    prop_assert_eq!(Err(e), Schematic::new(edited).map(|_| ()));
+                }
+                prop_assert_eq!(Schematic::new(schematic.grid().clone()), Ok(schematic.clone()));
+            }
+        }
+    }
+}
@@ -0,0 +1,239 @@
+//! Renderings of an engine schematic that tell part numbers, other numbers and gears apart.
+
+use std::fmt::Write;
+
+use common::grid::Grid;
+
+use crate::index::Index;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Empty,
+    PartNumber(usize),
+    OtherNumber(usize),
+    /// A `*` adjacent to exactly two numbers.
+    Gear(usize),
+    Symbol(usize),
+}
+
+impl Role {
+    const fn ansi(self) -> &'static str {
+        match self {
+            Self::Empty => "\x1b[2m",
+            Self::PartNumber(_) => "\x1b[32m",
+            Self::OtherNumber(_) => "\x1b[31m",
+            Self::Gear(_) => "\x1b[1;30;43m",
+            Self::Symbol(_) => "\x1b[1m",
+        }
+    }
+
+    const fn class(self) -> &'static str {
+        match self {
+            Self::Empty => "empty",
+            Self::PartNumber(_) => "part",
+            Self::OtherNumber(_) => "other",
+            Self::Gear(_) => "gear",
+            Self::Symbol(_) => "symbol",
+        }
+    }
+}
+
+fn roles(schematic: &Grid<u8>, index: &Index) -> Grid<Role> {
+    let mut roles = Grid::from_fn(schematic.width(), schematic.height(), |_| Role::Empty);
+    for (i, number) in index.numbers().iter().enumerate() {
+        let role = if index.symbols_around(i).is_empty() {
+            Role::OtherNumber(i)
+        } else {
+            Role::PartNumber(i)
+        };
+        let (x, y) = number.pos;
+        for x in x..x + number.len {
+            roles[(x, y)] = role;
+        }
+    }
+    for (i, symbol) in index.symbols().iter().enumerate() {
+        roles[symbol.pos] = if symbol.ch == '*' && index.numbers_around(i).len() == 2 {
+            Role::Gear(i)
+        } else {
+            Role::Symbol(i)
+        };
+    }
+    roles
+}
+
+/// The schematic as text colored with ANSI escape codes: part numbers in green, other numbers
+/// in red and gears highlighted in yellow.
+pub fn ansi(schematic: &Grid<u8>, index: &Index) -> String {
+    let roles = roles(schematic, index);
+    let mut out = String::new();
+    for (row, roles) in schematic.rows().zip(roles.rows()) {
+        let mut current = None;
+        for (&cell, &role) in row.iter().zip(roles) {
+            let style = role.ansi();
+            if current != Some(style) {
+                out.push_str("\x1b[0m");
+                out.push_str(style);
+                current = Some(style);
+            }
+            out.push(cell as char);
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 16;
+
+/// The schematic as an SVG image with the same colors as [`ansi`], and tooltips with the value
+/// and the adjacent symbols of every number, and the adjacent numbers of every symbol.
+pub fn svg(schematic: &Grid<u8>, index: &Index) -> String {
+    let roles = roles(schematic, index);
+    let mut out = String::new();
+    writeln!(
+        out,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="14">"#,
+        schematic.width() * CELL_WIDTH,
+        schematic.height() * CELL_HEIGHT,
+    )
+    .unwrap();
+    out.push_str(
+        "<style>\
+         .empty{fill:#bbb}.part{fill:#080}.other{fill:#c00}.symbol{fill:#000;font-weight:bold}\
+         .gear{fill:#000;font-weight:bold}rect.gear{fill:#fd0}\
+         </style>\n",
+    );
+
+    for (i, number) in index.numbers().iter().enumerate() {
+        let symbols: Vec<_> = index
+            .symbols_around(i)
+            .iter()
+            .map(|&s| {
+                let symbol = index.symbols()[s];
+                format!("{} at ({}, {})", symbol.ch, symbol.pos.0, symbol.pos.1)
+            })
+            .collect();
+        let title = if symbols.is_empty() {
+            format!("{}: not adjacent to any symbol", number.value)
+        } else {
+            format!("{}: adjacent to {}", number.value, symbols.join(", "))
+        };
+        let (x, y) = number.pos;
+        let digits = &schematic.row(y)[x..x + number.len];
+        writeln!(
+            out,
+            r#"<text class="{}" x="{}" y="{}"><title>{}</title>{}</text>"#,
+            roles[number.pos].class(),
+            x * CELL_WIDTH,
+            (y + 1) * CELL_HEIGHT - 4,
+            escape(&title),
+            std::str::from_utf8(digits).unwrap(),
+        )
+        .unwrap();
+    }
+
+    for (i, symbol) in index.symbols().iter().enumerate() {
+        let values: Vec<_> = index
+            .numbers_around(i)
+            .iter()
+            .map(|&n| index.numbers()[n].value.to_string())
+            .collect();
+        let role = roles[symbol.pos];
+        let title = match role {
+            Role::Gear(_) => format!(
+                "gear adjacent to {}, ratio {}",
+                values.join(", "),
+                index
+                    .numbers_around(i)
+                    .iter()
+                    .map(|&n| u64::from(index.numbers()[n].value))
+                    .product::<u64>()
+            ),
+            _ if values.is_empty() => "not adjacent to any number".to_owned(),
+            _ => format!("adjacent to {}", values.join(", ")),
+        };
+        let (x, y) = symbol.pos;
+        out.push_str("<g>");
+        write!(out, "<title>{}</title>", escape(&title)).unwrap();
+        if let Role::Gear(_) = role {
+            write!(
+                out,
+                r#"<rect class="gear" x="{}" y="{}" width="{CELL_WIDTH}" height="{CELL_HEIGHT}"/>"#,
+                x * CELL_WIDTH,
+                y * CELL_HEIGHT,
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            r#"<text class="{}" x="{}" y="{}">{}</text></g>"#,
+            role.class(),
+            x * CELL_WIDTH,
+            (y + 1) * CELL_HEIGHT - 4,
+            escape(&symbol.ch.to_string()),
+        )
+        .unwrap();
+    }
+
+    out.push_str("</svg>\n");
+    out
+}
+
+/// A standalone HTML page with the [`svg`] rendering.
+pub fn html(schematic: &Grid<u8>, index: &Index) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Engine schematic</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        svg(schematic, index)
+    )
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::schematic::{self, Padding};
+
+    const INPUT: &str = "467..114..
+...*......
+..35..633.";
+
+    #[test]
+    fn test_ansi() {
+        let schematic = schematic::parse(INPUT, Padding::Reject).unwrap();
+        let rendered = ansi(&schematic, &Index::new(&schematic));
+        assert_eq!(
+            "\x1b[0m\x1b[32m467\x1b[0m\x1b[2m..\x1b[0m\x1b[31m114\x1b[0m\x1b[2m..\x1b[0m\n\
+             \x1b[0m\x1b[2m...\x1b[0m\x1b[1;30;43m*\x1b[0m\x1b[2m......\x1b[0m\n\
+             \x1b[0m\x1b[2m..\x1b[0m\x1b[32m35\x1b[0m\x1b[2m..\x1b[0m\x1b[31m633\x1b[0m\x1b[2m.\x1b[0m\n",
+            rendered
+        );
+    }
+
+    #[test]
+    fn test_svg() {
+        let schematic = schematic::parse(INPUT, Padding::Reject).unwrap();
+        let rendered = svg(&schematic, &Index::new(&schematic));
+        assert!(rendered.contains(
+            r#"<text class="part" x="0" y="12"><title>467: adjacent to * at (3, 1)</title>467</text>"#
+        ));
+        assert!(rendered.contains(r#"<title>114: not adjacent to any symbol</title>"#));
+        assert!(rendered.contains(r#"<title>gear adjacent to 467, 35, ratio 16345</title>"#));
+        assert!(rendered.ends_with("</svg>\n"));
+    }
+
+    #[test]
+    fn test_svg_large_ratio() {
+        let schematic = schematic::parse("70000*70000", Padding::Reject).unwrap();
+        let rendered = svg(&schematic, &Index::new(&schematic));
+        assert!(
+            rendered.contains(r#"<title>gear adjacent to 70000, 70000, ratio 4900000000</title>"#)
+        );
+    }
+}
@@ -0,0 +1,278 @@
+use common::grid::{Grid, Pos};
+
+/// A number in the engine schematic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Number {
+    /// Position of the leftmost digit.
+    pub pos: Pos,
+    /// Amount of digits.
+    pub len: usize,
+    pub value: u32,
+}
+
+impl Number {
+    /// Positions around the number inside the schematic.
+    pub fn neighbours<'a>(&self, schematic: &'a Grid<u8>) -> impl Iterator<Item = Pos> + 'a {
+        let (x, y) = self.pos;
+        let len = self.len;
+        (-1..=1)
+            .flat_map(move |dy| (-1..=len as isize).map(move |dx| (dx, dy)))
+            .filter(move |&(dx, dy)| dy != 0 || dx < 0 || dx == len as isize)
+            .filter_map(move |offset| schematic.offset((x, y), offset))
+    }
+}
+
+/// How rows shorter than the longest one are treated.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Padding {
+    /// Every row must be as wide as the first one.
+    #[default]
+    Reject,
+    /// Shorter rows are padded with `.` up to the width of the longest row.
+    Dots,
+}
+
+/// Malformed schematic. Rows and columns are one-based, columns count characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum SchematicError {
+    #[error("row {row} is {found} characters wide, expected {expected}")]
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("row {row}, column {column}: non-ASCII character {ch:?}")]
+    NonAscii { row: usize, column: usize, ch: char },
+    #[error("row {row}, column {column}: unexpected character {ch:?}")]
+    InvalidCharacter { row: usize, column: usize, ch: char },
+    #[error("row {row}, column {column}: the number does not fit in 32 bits")]
+    NumberTooLarge { row: usize, column: usize },
+}
+
+/// Parses the schematic, accepting both LF and CRLF line endings.
+///
+/// Every cell must be a printable ASCII character other than space, and every number
+/// must fit in a `u32`.
+pub fn parse(input: &str, padding: Padding) -> Result<Grid<u8>, SchematicError> {
+    let input = input.strip_suffix('\n').unwrap_or(input);
+    let mut rows = Vec::new();
+    if !input.is_empty() {
+        for (i, line) in input.split('\n').enumerate() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            if let Some((column, ch)) = line
+                .chars()
+                .enumerate()
+                .find(|(_, ch)| !ch.is_ascii_graphic())
+            {
+                let (row, column) = (i + 1, column + 1);
+                return Err(if ch.is_ascii() {
+                    SchematicError::InvalidCharacter { row, column, ch }
+                } else {
+                    SchematicError::NonAscii { row, column, ch }
+                });
+            }
+            rows.push(line.as_bytes());
+        }
+    }
+
+    let width = match padding {
+        Padding::Reject => rows.first().map_or(0, |row| row.len()),
+        Padding::Dots => rows.iter().map(|row| row.len()).max().unwrap_or(0),
+    };
+    let mut cells = Vec::with_capacity(width * rows.len());
+    for (i, row) in rows.iter().enumerate() {
+        if padding == Padding::Reject && row.len() != width {
+            return Err(SchematicError::Ragged {
+                row: i + 1,
+                expected: width,
+                found: row.len(),
+            });
+        }
+        cells.extend_from_slice(row);
+        cells.resize(width * (i + 1), b'.');
+    }
+    let schematic = Grid::new(width, rows.len(), cells);
+    check_numbers(&schematic)?;
+    Ok(schematic)
+}
+
+/// Fails on the first number that does not fit in a `u32`.
+pub(crate) fn check_numbers(schematic: &Grid<u8>) -> Result<(), SchematicError> {
+    for (y, row) in schematic.rows().enumerate() {
+        let mut x = 0;
+        while let Some(start) = row[x..].iter().position(u8::is_ascii_digit) {
+            x += start;
+            let number = try_number_from(row, (x, y)).ok_or(SchematicError::NumberTooLarge {
+                row: y + 1,
+                column: x + 1,
+            })?;
+            x += number.len;
+        }
+    }
+    Ok(())
+}
+
+pub const fn is_symbol(cell: u8) -> bool {
+    cell != b'.' && !cell.is_ascii_digit()
+}
+
+/// The numbers of the schematic, row by row.
+///
+/// # Panics
+///
+/// If a number does not fit in a `u32`, which [`parse`] rejects.
+pub fn numbers(schematic: &Grid<u8>) -> impl Iterator<Item = Number> + '_ {
+    schematic.rows().enumerate().flat_map(move |(y, row)| {
+        let mut x = 0;
+        std::iter::from_fn(move || {
+            x += row[x..].iter().position(u8::is_ascii_digit)?;
+            let number = number_from(row, (x, y));
+            x += number.len;
+            Some(number)
+        })
+    })
+}
+
+/// Fails if the number with a digit at `pos`, if any, does not fit in a `u32`.
+pub(crate) fn check_number_at(schematic: &Grid<u8>, (x, y): Pos) -> Result<(), SchematicError> {
+    let row = schematic.row(y);
+    if !row[x].is_ascii_digit() {
+        return Ok(());
+    }
+    let start = number_start(row, x);
+    try_number_from(row, (start, y))
+        .map(|_| ())
+        .ok_or(SchematicError::NumberTooLarge {
+            row: y + 1,
+            column: start + 1,
+        })
+}
+
+/// The number with a digit at `pos`, if any.
+///
+/// # Panics
+///
+/// If the number does not fit in a `u32`, which [`parse`] rejects.
+pub fn number_at(schematic: &Grid<u8>, (x, y): Pos) -> Option<Number> {
+    let row = schematic.row(y);
+    if !row[x].is_ascii_digit() {
+        return None;
+    }
+    Some(number_from(row, (number_start(row, x), y)))
+}
+
+/// Where the run of digits through `x` starts in the `row`.
+fn number_start(row: &[u8], x: usize) -> usize {
+    row[..x]
+        .iter()
+        .rposition(|cell| !cell.is_ascii_digit())
+        .map_or(0, |i| i + 1)
+}
+
+/// The number starting at `pos` in the `row`.
+fn number_from(row: &[u8], pos: Pos) -> Number {
+    try_number_from(row, pos).expect("the number does not fit in a u32")
+}
+
+/// The number starting at `pos` in the `row`, if it fits in a `u32`.
+fn try_number_from(row: &[u8], pos: Pos) -> Option<Number> {
+    let mut digits = row[pos.0..].iter().take_while(|cell| cell.is_ascii_digit());
+    let (len, value) = digits.try_fold((0, 0u32), |(len, value), digit| {
+        let value = value
+            .checked_mul(10)?
+            .checked_add(u32::from(digit - b'0'))?;
+        Some((len + 1, value))
+    })?;
+    Some(Number { pos, len, value })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[test]
+    fn test_numbers() {
+        let schematic = parse("467..114..\n...*......\n..35..633.", Padding::Reject).unwrap();
+        assert_eq!(
+            vec![
+                Number {
+                    pos: (0, 0),
+                    len: 3,
+                    value: 467
+                },
+                Number {
+                    pos: (5, 0),
+                    len: 3,
+                    value: 114
+                },
+                Number {
+                    pos: (2, 2),
+                    len: 2,
+                    value: 35
+                },
+                Number {
+                    pos: (6, 2),
+                    len: 3,
+                    value: 633
+                },
+            ],
+            numbers(&schematic).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(Number {
+                pos: (6, 2),
+                len: 3,
+                value: 633
+            }),
+            number_at(&schematic, (8, 2))
+        );
+        assert_eq!(None, number_at(&schematic, (3, 1)));
+        assert_eq!(
+            vec![(4, 0), (8, 0), (4, 1), (5, 1), (6, 1), (7, 1), (8, 1)],
+            Number {
+                pos: (5, 0),
+                len: 3,
+                value: 114
+            }
+            .neighbours(&schematic)
+            .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_long_number() {
+        let schematic = parse("*4294967295.\n...........1", Padding::Reject).unwrap();
+        assert_eq!(
+            vec![4294967295, 1],
+            numbers(&schematic)
+                .map(|number| number.value)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case("467..114..\n...*......\n", Padding::Reject)]
+    #[case("467..114..\r\n...*......\r\n", Padding::Reject)]
+    #[case("467..114..\n...*......", Padding::Dots)]
+    #[case("467..114..\r\n...*", Padding::Dots)]
+    fn test_parse(#[case] input: &str, #[case] padding: Padding) {
+        assert_eq!(
+            Grid::parse_bytes("467..114..\n...*......").unwrap(),
+            parse(input, padding).unwrap()
+        );
+    }
+
+    #[rstest]
+    #[case("467..114..\n...*.....", SchematicError::Ragged { row: 2, expected: 10, found: 9 })]
+    #[case("467..114..\n...*......\n\n", SchematicError::Ragged { row: 3, expected: 10, found: 0 })]
+    #[case("467..114..\n..é*......", SchematicError::NonAscii { row: 2, column: 3, ch: 'é' })]
+    #[case("467..114..\n...*.. ...", SchematicError::InvalidCharacter { row: 2, column: 7, ch: ' ' })]
+    #[case("467..114..\r...*......", SchematicError::InvalidCharacter { row: 1, column: 11, ch: '\r' })]
+    #[case("467..114..\n..........\n4294967296", SchematicError::NumberTooLarge { row: 3, column: 1 })]
+    #[case("12345678901234567890123", SchematicError::NumberTooLarge { row: 1, column: 1 })]
+    fn test_parse_malformed(#[case] input: &str, #[case] expected: SchematicError) {
+        assert_eq!(Err(expected), parse(input, Padding::Reject));
+    }
+}
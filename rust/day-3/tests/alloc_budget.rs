@@ -0,0 +1,27 @@
+//! Run with `cargo test -p day-3 --features dhat-heap --test alloc_budget`.
+#![cfg(feature = "dhat-heap")]
+
+use common::alloc_budget::{check, Budget};
+use day_3::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &str = include_str!("../input.txt");
+
+const PART1: Budget<u64> = Budget {
+    answer: 521515,
+    total_blocks: 60,
+    max_bytes: 735784,
+};
+const PART2: Budget<u64> = Budget {
+    answer: 69527306,
+    total_blocks: 60,
+    max_bytes: 735784,
+};
+
+#[test]
+fn process_stays_within_budget() {
+    check("part1", INPUT, PART1, part1::process);
+    check("part2", INPUT, PART2, part2::process);
+}
@@ -0,0 +1,14 @@
+use day_4::part1::process;
+
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+fn main() {
+    #[cfg(feature = "dhat-heap")]
+    let _profiler = dhat::Profiler::new_heap();
+
+    let file = include_str!("../../input.txt");
+    let result = process(file);
+    println!("{}", result);
+}
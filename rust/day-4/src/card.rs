@@ -0,0 +1,103 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, line_ending, space0, space1},
+    combinator::{map, map_res},
+    multi::separated_list1,
+    sequence::{delimited, preceded, separated_pair, tuple},
+    IResult,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Card {
+    pub id: u32,
+    pub winning: Vec<u32>,
+    pub have: Vec<u32>,
+}
+
+impl Card {
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let id = delimited(tuple((tag("Card"), space1)), u32, tag(":"));
+        let numbers = || preceded(space0, separated_list1(space1, u32));
+        map(
+            tuple((
+                id,
+                separated_pair(numbers(), tuple((space0, tag("|"))), numbers()),
+            )),
+            |(id, (winning, have))| Self { id, winning, have },
+        )(input)
+    }
+
+    pub fn parse_all(input: &str) -> IResult<&str, Vec<Self>> {
+        separated_list1(line_ending, Self::parse)(input)
+    }
+
+    /// Amount of the numbers we have that are winning.
+    pub fn matches(&self) -> usize {
+        self.have
+            .iter()
+            .filter(|number| self.winning.contains(number))
+            .count()
+    }
+
+    /// One point for the first match, doubled for every match after it.
+    ///
+    /// `None` for more than 64 matches, whose points do not fit in a `u64`.
+    pub fn points(&self) -> Option<u64> {
+        match self.matches() {
+            0 => Some(0),
+            matches => 1u64.checked_shl(u32::try_from(matches - 1).ok()?),
+        }
+    }
+}
+
+fn u32(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, str::parse)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Card {
+                id: 3,
+                winning: vec![1, 21, 53, 59, 44],
+                have: vec![69, 82, 63, 72, 16, 21, 14, 1],
+            },
+            Card::parse("Card   3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1")
+                .unwrap()
+                .1
+        );
+    }
+
+    #[rstest]
+    #[case("Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", 4, 8)]
+    #[case("Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19", 2, 2)]
+    #[case("Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1", 2, 2)]
+    #[case("Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83", 1, 1)]
+    #[case("Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36", 0, 0)]
+    #[case("Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11", 0, 0)]
+    fn test_card(#[case] card: &str, #[case] matches: usize, #[case] points: u64) {
+        let (_, card) = Card::parse(card).unwrap();
+        assert_eq!(matches, card.matches());
+        assert_eq!(Some(points), card.points());
+    }
+
+    #[rstest]
+    #[case(32, Some(1 << 31))]
+    #[case(33, Some(1 << 32))]
+    #[case(64, Some(1 << 63))]
+    #[case(65, None)]
+    fn test_points_of_large_card(#[case] matches: u32, #[case] expected: Option<u64>) {
+        let card = Card {
+            id: 1,
+            winning: (1..=matches).collect(),
+            have: (1..=matches).collect(),
+        };
+        assert_eq!(expected, card.points());
+    }
+}
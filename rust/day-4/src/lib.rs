@@ -0,0 +1,3 @@
+pub mod card;
+pub mod part1;
+pub mod part2;
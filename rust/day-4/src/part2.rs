@@ -0,0 +1,42 @@
+use crate::card::Card;
+
+pub fn process(input: &str) -> usize {
+    let (_, cards) = Card::parse_all(input).expect("Invalid input");
+    total_cards(&cards)
+}
+
+/// Total amount of cards after every card has won copies of the cards following it.
+///
+/// Each card adds its copies to a range of the following cards, so instead of visiting the range
+/// only the amount of copies that stop being added after it is recorded.
+fn total_cards(cards: &[Card]) -> usize {
+    let mut expiring = vec![0; cards.len() + 1];
+    let mut extra = 0;
+    let mut total = 0;
+    for (i, card) in cards.iter().enumerate() {
+        extra -= expiring[i];
+        let copies = 1 + extra;
+        total += copies;
+
+        let won = card.matches().min(cards.len() - i - 1);
+        extra += copies;
+        expiring[i + 1 + won] += copies;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+        assert_eq!(30, process(input));
+    }
+}
@@ -0,0 +1,27 @@
+//! Run with `cargo test -p day-4 --features dhat-heap --test alloc_budget`.
+#![cfg(feature = "dhat-heap")]
+
+use common::alloc_budget::{check, Budget};
+use day_4::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &str = include_str!("../input.txt");
+
+const PART1: Budget<u64> = Budget {
+    answer: 24848,
+    total_blocks: 1463,
+    max_bytes: 54272,
+};
+const PART2: Budget<usize> = Budget {
+    answer: 7258152,
+    total_blocks: 1464,
+    max_bytes: 55944,
+};
+
+#[test]
+fn process_stays_within_budget() {
+    check("part1", INPUT, PART1, part1::process);
+    check("part2", INPUT, PART2, part2::process);
+}
@@ -1,2 +1,3 @@
+mod almanac;
 pub mod part1;
 pub mod part2;
@@ -1,15 +1,71 @@
-pub fn process(_input: &str) -> u32 {
-    todo!("day-5 - part 1");
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending},
+    combinator::map_res,
+    multi::separated_list1,
+    sequence::{delimited, pair},
+};
+
+use crate::almanac::Mappings;
+
+pub fn process(input: &str) -> u32 {
+    let seed = map_res(digit1, |input: &str| input.parse::<u32>());
+    let (_, (seeds, mappings)) = pair(
+        delimited(
+            tag("seeds: "),
+            separated_list1(char(' '), seed),
+            pair(line_ending, line_ending),
+        ),
+        Mappings::parse,
+    )(input)
+    .expect("invalid input");
+
+    seeds
+        .into_iter()
+        .map(|seed| mappings.apply(seed))
+        .min()
+        .expect("invalid input")
 }
 
 #[cfg(test)]
 mod tests {
-    // use super::*;
+    use super::*;
 
     #[test]
     fn test_process() {
-        todo!("haven't built test yet");
-        // let input = "";
-        // assert_eq!(0, process(input));
+        let input = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+        assert_eq!(35, process(input));
     }
 }
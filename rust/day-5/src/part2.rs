@@ -1,15 +1,15 @@
-use std::cmp::Ordering;
-
 use nom::{
     bytes::complete::tag,
     character::complete::{char, digit1, line_ending},
     combinator::{map, map_res},
-    multi::{separated_list0, separated_list1},
-    sequence::{delimited, pair, preceded, separated_pair, tuple},
+    multi::separated_list1,
+    sequence::{delimited, pair, separated_pair},
     IResult,
 };
 use rayon::prelude::*;
 
+use crate::almanac::Mappings;
+
 pub fn process(input: &str) -> u32 {
     let (_, (seeds, mappings)) = pair(
         delimited(tag("seeds: "), Seeds::parse, pair(line_ending, line_ending)),
@@ -24,86 +24,6 @@ pub fn process(input: &str) -> u32 {
         .expect("invalid input")
 }
 
-struct Mappings {
-    seed_to_soil: Map,
-    soil_to_fertilizer: Map,
-    fertilizer_to_water: Map,
-    water_to_light: Map,
-    light_to_temperature: Map,
-    temperature_to_humidity: Map,
-    humidity_to_location: Map,
-}
-
-impl Mappings {
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(
-            tuple((
-                delimited(
-                    pair(tag("seed-to-soil map:"), line_ending),
-                    Map::parse,
-                    pair(line_ending, line_ending),
-                ),
-                delimited(
-                    pair(tag("soil-to-fertilizer map:"), line_ending),
-                    Map::parse,
-                    pair(line_ending, line_ending),
-                ),
-                delimited(
-                    pair(tag("fertilizer-to-water map:"), line_ending),
-                    Map::parse,
-                    pair(line_ending, line_ending),
-                ),
-                delimited(
-                    pair(tag("water-to-light map:"), line_ending),
-                    Map::parse,
-                    pair(line_ending, line_ending),
-                ),
-                delimited(
-                    pair(tag("light-to-temperature map:"), line_ending),
-                    Map::parse,
-                    pair(line_ending, line_ending),
-                ),
-                delimited(
-                    pair(tag("temperature-to-humidity map:"), line_ending),
-                    Map::parse,
-                    pair(line_ending, line_ending),
-                ),
-                preceded(
-                    pair(tag("humidity-to-location map:"), line_ending),
-                    Map::parse,
-                ),
-            )),
-            |(
-                seed_to_soil,
-                soil_to_fertilizer,
-                fertilizer_to_water,
-                water_to_light,
-                light_to_temperature,
-                temperature_to_humidity,
-                humidity_to_location,
-            )| Self {
-                seed_to_soil,
-                soil_to_fertilizer,
-                fertilizer_to_water,
-                water_to_light,
-                light_to_temperature,
-                temperature_to_humidity,
-                humidity_to_location,
-            },
-        )(input)
-    }
-
-    fn apply(&self, seed: u32) -> u32 {
-        let soil = self.seed_to_soil.apply(seed);
-        let fertilizer = self.soil_to_fertilizer.apply(soil);
-        let water = self.fertilizer_to_water.apply(fertilizer);
-        let light = self.water_to_light.apply(water);
-        let temperature = self.light_to_temperature.apply(light);
-        let humidity = self.temperature_to_humidity.apply(temperature);
-        return self.humidity_to_location.apply(humidity);
-    }
-}
-
 #[derive(Debug, Clone, Copy)]
 struct SeedsRange {
     start: u32,
@@ -156,7 +76,7 @@ impl IntoIterator for Seeds {
     type IntoIter = std::iter::Flatten<std::vec::IntoIter<SeedsRange>>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.ranges.into_iter().flatten().into_iter()
+        self.ranges.into_iter().flatten()
     }
 }
 
@@ -169,74 +89,6 @@ impl IntoParallelIterator for Seeds {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-struct RangeMap {
-    destination_start: u32,
-    source_start: u32,
-    len: u32,
-}
-
-impl RangeMap {
-    const fn apply(self, source: u32) -> Option<u32> {
-        if self.source_start <= source && source - self.source_start < self.len {
-            Some(self.destination_start + (source - self.source_start))
-        } else {
-            None
-        }
-    }
-
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let destination_start = map_res(digit1, |input: &str| input.parse::<u32>());
-        let source_start = map_res(digit1, |input: &str| input.parse::<u32>());
-        let len = map_res(digit1, |input: &str| input.parse::<u32>());
-        map(
-            tuple((destination_start, char(' '), source_start, char(' '), len)),
-            |(destination_start, _, source_start, _, len)| Self {
-                destination_start,
-                source_start,
-                len,
-            },
-        )(input)
-    }
-}
-
-struct Map {
-    ranges: Box<[RangeMap]>,
-}
-
-impl Map {
-    fn apply(&self, source: u32) -> u32 {
-        self.ranges
-            .binary_search_by(|range| {
-                if range.source_start > source {
-                    Ordering::Greater
-                } else if source - range.source_start >= range.len {
-                    Ordering::Less
-                } else {
-                    Ordering::Equal
-                }
-            })
-            .map(|range| {
-                unsafe { self.ranges.get_unchecked(range) }
-                    .apply(source)
-                    .unwrap()
-            })
-            .unwrap_or(source)
-    }
-
-    fn parse(input: &str) -> IResult<&str, Self> {
-        map(
-            separated_list0(line_ending, RangeMap::parse),
-            |mut ranges| {
-                ranges.sort_unstable_by_key(|range| range.source_start);
-                Self {
-                    ranges: ranges.into_boxed_slice(),
-                }
-            },
-        )(input)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
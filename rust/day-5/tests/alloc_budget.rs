@@ -0,0 +1,61 @@
+//! Run with `cargo test -p day-5 --features dhat-heap --test alloc_budget`.
+#![cfg(feature = "dhat-heap")]
+
+use common::alloc_budget::{check, Budget};
+use day_5::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &str = include_str!("../input.txt");
+/// Part 2 takes minutes on the input, so its budget is on the example.
+const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+const PART1: Budget<u32> = Budget {
+    answer: 107430936,
+    total_blocks: 42,
+    max_bytes: 2840,
+};
+const PART2: Budget<u32> = Budget {
+    answer: 46,
+    total_blocks: 14,
+    max_bytes: 272,
+};
+
+#[test]
+fn process_stays_within_budget() {
+    check("part1", INPUT, PART1, part1::process);
+    check("part2", EXAMPLE, PART2, part2::process);
+}
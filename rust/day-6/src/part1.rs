@@ -0,0 +1,18 @@
+use crate::race::{Interpretation, Race};
+
+pub fn process(input: &str) -> u64 {
+    let (_, races) = Race::parse(input, Interpretation::Races).expect("Invalid input");
+    races.iter().map(Race::ways_to_win).product()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "Time:      7  15   30
+Distance:  9  40  200";
+        assert_eq!(288, process(input));
+    }
+}
@@ -0,0 +1,228 @@
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, line_ending, space0, space1},
+    combinator::{map_res, verify},
+    multi::separated_list1,
+    sequence::{delimited, pair, preceded},
+    IResult,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Race {
+    /// Duration of the race in milliseconds.
+    pub time: u64,
+    /// Best distance so far in millimeters.
+    pub record: u64,
+}
+
+/// How the numbers on the sheet of paper are read.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Interpretation {
+    /// Every column is a separate race.
+    #[default]
+    Races,
+    /// The spaces are bad kerning, so every line is a single number of a single race.
+    Kerning,
+}
+
+impl Race {
+    /// Parses the `Time:` and `Distance:` lines.
+    pub fn parse(input: &str, interpretation: Interpretation) -> IResult<&str, Vec<Self>> {
+        let (rest, (times, records)) = sheet(input)?;
+        let races = match interpretation {
+            Interpretation::Races => times
+                .iter()
+                .zip(&records)
+                .map(|(time, record)| Self {
+                    time: time.parse().unwrap(),
+                    record: record.parse().unwrap(),
+                })
+                .collect(),
+            Interpretation::Kerning => {
+                let join = |numbers: &[&str]| numbers.concat().parse();
+                match (join(&times), join(&records)) {
+                    (Ok(time), Ok(record)) => vec![Self { time, record }],
+                    _ => {
+                        return Err(nom::Err::Failure(nom::error::Error::new(
+                            input,
+                            nom::error::ErrorKind::TooLarge,
+                        )))
+                    }
+                }
+            }
+        };
+        Ok((rest, races))
+    }
+
+    /// Amount of button hold times that beat the record.
+    ///
+    /// Holding the button for `t` covers `t * (time - t)`, so the winning hold times lie strictly
+    /// between the roots of `t^2 - time * t + record`, `(time ± sqrt(time^2 - 4 * record)) / 2`.
+    /// The roots are found with an integer square root, so the bounds are exact.
+    pub fn ways_to_win(&self) -> u64 {
+        let (time, record) = (u128::from(self.time), u128::from(self.record));
+        let Some(discriminant) = (time * time).checked_sub(4 * record) else {
+            return 0;
+        };
+        // `first` starts at most one below the smallest winning hold time
+        let mut first = (time - isqrt(discriminant)) / 2;
+        while first <= time / 2 && first * (time - first) <= record {
+            first += 1;
+        }
+        if first > time / 2 {
+            return 0;
+        }
+        // the winning hold times are symmetric around `time / 2`
+        (time - 2 * first + 1) as u64
+    }
+}
+
+/// The numbers of the `Time:` and `Distance:` lines, which must be equally many.
+fn sheet(input: &str) -> IResult<&str, (Vec<&str>, Vec<&str>)> {
+    let numbers = || preceded(space0, separated_list1(space1, digit1::<&str, _>));
+    let validated = |numbers| map_res(numbers, validate);
+    verify(
+        pair(
+            delimited(tag("Time:"), validated(numbers()), line_ending),
+            preceded(tag("Distance:"), validated(numbers())),
+        ),
+        |(times, records): &(Vec<&str>, Vec<&str>)| times.len() == records.len(),
+    )(input)
+}
+
+/// Rejects the numbers that do not fit a `u64` on their own.
+fn validate(numbers: Vec<&str>) -> Result<Vec<&str>, std::num::ParseIntError> {
+    numbers
+        .iter()
+        .try_for_each(|number| number.parse::<u64>().map(drop))?;
+    Ok(numbers)
+}
+
+/// The largest `s` with `s * s <= n`.
+fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    // Newton's method from above converges to the floor of the root
+    let mut x = 1 << (n.ilog2() / 2 + 1);
+    loop {
+        let y = (x + n / x) / 2;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use proptest::prelude::*;
+    use rstest::rstest;
+
+    const INPUT: &str = "Time:      7  15   30
+Distance:  9  40  200";
+
+    fn brute_force(race: Race) -> u64 {
+        (0..=race.time)
+            .filter(|&t| u128::from(t) * u128::from(race.time - t) > u128::from(race.record))
+            .count() as u64
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            vec![
+                Race { time: 7, record: 9 },
+                Race {
+                    time: 15,
+                    record: 40
+                },
+                Race {
+                    time: 30,
+                    record: 200
+                },
+            ],
+            Race::parse(INPUT, Interpretation::Races).unwrap().1
+        );
+        assert_eq!(
+            vec![Race {
+                time: 71530,
+                record: 940200
+            }],
+            Race::parse(INPUT, Interpretation::Kerning).unwrap().1
+        );
+    }
+
+    #[rstest]
+    #[case("Time:      7  15\nDistance:  9  40  200", Interpretation::Races)]
+    #[case(
+        "Time:      7  15  30\nDistance:  9  40  99999999999999999999",
+        Interpretation::Races
+    )]
+    #[case(
+        "Time:      7  15  30000000000000000\nDistance:  9  40  2",
+        Interpretation::Kerning
+    )]
+    fn test_parse_invalid(#[case] input: &str, #[case] interpretation: Interpretation) {
+        assert!(Race::parse(input, interpretation).is_err());
+    }
+
+    #[rstest]
+    #[case(7, 9, 4)]
+    #[case(15, 40, 8)]
+    #[case(71530, 940200, 71503)]
+    // the roots are integers: 10 and 20 only tie the record
+    #[case(30, 200, 9)]
+    #[case(4, 4, 0)]
+    #[case(4, 3, 1)]
+    #[case(5, 6, 0)]
+    #[case(5, 5, 2)]
+    #[case(0, 0, 0)]
+    #[case(1, 0, 0)]
+    #[case(2, 0, 1)]
+    #[case(u64::MAX, 0, u64::MAX - 1)]
+    #[case(u64::MAX, u64::MAX, u64::MAX - 3)]
+    fn test_ways_to_win(#[case] time: u64, #[case] record: u64, #[case] expected: u64) {
+        assert_eq!(expected, Race { time, record }.ways_to_win());
+    }
+
+    #[test]
+    fn test_ways_to_win_around_max_distance() {
+        let time = 4_000_000_000u64;
+        let max = (time / 2) * (time / 2);
+        assert_eq!(
+            1,
+            Race {
+                time,
+                record: max - 1
+            }
+            .ways_to_win()
+        );
+        assert_eq!(0, Race { time, record: max }.ways_to_win());
+        assert_eq!(
+            3,
+            Race {
+                time,
+                record: max - 2
+            }
+            .ways_to_win()
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn ways_to_win_matches_brute_force(time in 0..2000u64, record in 0..1_000_000u64) {
+            let race = Race { time, record };
+            prop_assert_eq!(brute_force(race), race.ways_to_win());
+        }
+
+        #[test]
+        fn isqrt_is_floor_of_root(n: u128) {
+            let root = isqrt(n);
+            prop_assert!(root * root <= n);
+            prop_assert!((root + 1).checked_mul(root + 1).is_none_or(|square| square > n));
+        }
+    }
+}
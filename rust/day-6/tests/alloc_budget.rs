@@ -0,0 +1,27 @@
+//! Run with `cargo test -p day-6 --features dhat-heap --test alloc_budget`.
+#![cfg(feature = "dhat-heap")]
+
+use common::alloc_budget::{check, Budget};
+use day_6::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &str = include_str!("../input.txt");
+
+const PART1: Budget<u64> = Budget {
+    answer: 6209190,
+    total_blocks: 3,
+    max_bytes: 192,
+};
+const PART2: Budget<u64> = Budget {
+    answer: 28545089,
+    total_blocks: 5,
+    max_bytes: 144,
+};
+
+#[test]
+fn process_stays_within_budget() {
+    check("part1", INPUT, PART1, part1::process);
+    check("part2", INPUT, PART2, part2::process);
+}
@@ -0,0 +1,143 @@
+use nom::{
+    character::complete::{digit1, line_ending, one_of, space1},
+    combinator::{map, map_res},
+    multi::{count, separated_list1},
+    sequence::separated_pair,
+    IResult,
+};
+
+use crate::rules::Rules;
+
+/// Card labels in the order of their standard strength.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Label {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+impl Label {
+    pub const ALL: [Self; 13] = [
+        Self::Two,
+        Self::Three,
+        Self::Four,
+        Self::Five,
+        Self::Six,
+        Self::Seven,
+        Self::Eight,
+        Self::Nine,
+        Self::Ten,
+        Self::Jack,
+        Self::Queen,
+        Self::King,
+        Self::Ace,
+    ];
+
+    pub const fn from_char(ch: char) -> Option<Self> {
+        Some(match ch {
+            '2' => Self::Two,
+            '3' => Self::Three,
+            '4' => Self::Four,
+            '5' => Self::Five,
+            '6' => Self::Six,
+            '7' => Self::Seven,
+            '8' => Self::Eight,
+            '9' => Self::Nine,
+            'T' => Self::Ten,
+            'J' => Self::Jack,
+            'Q' => Self::Queen,
+            'K' => Self::King,
+            'A' => Self::Ace,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hand(pub [Label; 5]);
+
+impl Hand {
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let label = map(one_of("23456789TJQKA"), |ch| Label::from_char(ch).unwrap());
+        map(count(label, 5), |labels| {
+            Self(labels.try_into().expect("exactly 5 labels"))
+        })(input)
+    }
+
+    /// How many times every label occurs in the hand, indexed by the label.
+    pub fn counts(&self) -> [u8; 13] {
+        let mut counts = [0; 13];
+        for label in self.0 {
+            counts[label as usize] += 1;
+        }
+        counts
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub hand: Hand,
+    pub bid: u32,
+}
+
+impl Entry {
+    pub fn parse(input: &str) -> IResult<&str, Self> {
+        let bid = map_res(digit1, str::parse);
+        map(separated_pair(Hand::parse, space1, bid), |(hand, bid)| {
+            Self { hand, bid }
+        })(input)
+    }
+
+    pub fn parse_all(input: &str) -> IResult<&str, Vec<Self>> {
+        separated_list1(line_ending, Self::parse)(input)
+    }
+}
+
+/// Sum of the bids multiplied by the ranks of their hands under the rules `R`.
+pub fn total_winnings<R: Rules>(entries: &[Entry]) -> u64 {
+    let mut keyed: Vec<(u32, u32)> = entries
+        .iter()
+        .map(|entry| (R::key(&entry.hand), entry.bid))
+        .collect();
+    keyed.sort_unstable();
+    keyed
+        .iter()
+        .zip(1..)
+        .map(|(&(_, bid), rank)| rank * u64::from(bid))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        use Label::*;
+        assert_eq!(
+            vec![
+                Entry {
+                    hand: Hand([Three, Two, Ten, Three, King]),
+                    bid: 765
+                },
+                Entry {
+                    hand: Hand([Ten, Five, Five, Jack, Five]),
+                    bid: 684
+                },
+            ],
+            Entry::parse_all("32T3K 765\nT55J5 684").unwrap().1
+        );
+        assert!(Hand::parse("32T3").is_err());
+        assert!(Hand::parse("32T3X").is_err());
+    }
+}
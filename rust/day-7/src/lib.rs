@@ -0,0 +1,4 @@
+pub mod hand;
+pub mod part1;
+pub mod part2;
+pub mod rules;
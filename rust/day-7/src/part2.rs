@@ -0,0 +1,24 @@
+use crate::{
+    hand::{total_winnings, Entry},
+    rules::JokersWild,
+};
+
+pub fn process(input: &str) -> u64 {
+    let (_, entries) = Entry::parse_all(input).expect("Invalid input");
+    total_winnings::<JokersWild>(&entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process() {
+        let input = "32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483";
+        assert_eq!(5905, process(input));
+    }
+}
@@ -0,0 +1,166 @@
+use crate::hand::{Hand, Label};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandType {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    FullHouse,
+    FourOfAKind,
+    FiveOfAKind,
+}
+
+impl HandType {
+    /// The type of a hand whose two largest groups of equal labels have the given sizes.
+    pub const fn from_groups(largest: u8, second: u8) -> Self {
+        match (largest, second) {
+            (5, _) => Self::FiveOfAKind,
+            (4, _) => Self::FourOfAKind,
+            (3, 2) => Self::FullHouse,
+            (3, _) => Self::ThreeOfAKind,
+            (2, 2) => Self::TwoPair,
+            (2, _) => Self::OnePair,
+            _ => Self::HighCard,
+        }
+    }
+}
+
+/// How hands are typed and how labels are ordered when breaking ties between hands of the same
+/// type.
+pub trait Rules {
+    /// Strength of the label, from 0 to 12.
+    fn strength(label: Label) -> u8;
+
+    fn hand_type(hand: &Hand) -> HandType;
+
+    /// A key that orders hands by their type and then by the strengths of their labels
+    /// from first to last: four bits per label below the type.
+    fn key(hand: &Hand) -> u32 {
+        hand.0
+            .iter()
+            .fold(Self::hand_type(hand) as u32, |key, &label| {
+                key << 4 | u32::from(Self::strength(label))
+            })
+    }
+}
+
+/// `J` is a jack, ranked between `T` and `Q`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Standard;
+
+impl Rules for Standard {
+    fn strength(label: Label) -> u8 {
+        label as u8
+    }
+
+    fn hand_type(hand: &Hand) -> HandType {
+        let (largest, second) = two_largest(hand.counts());
+        HandType::from_groups(largest, second)
+    }
+}
+
+/// `J` is a joker, which is the weakest label on its own but joins the largest group of the hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JokersWild;
+
+impl Rules for JokersWild {
+    fn strength(label: Label) -> u8 {
+        match label {
+            Label::Jack => 0,
+            label if label < Label::Jack => label as u8 + 1,
+            label => label as u8,
+        }
+    }
+
+    fn hand_type(hand: &Hand) -> HandType {
+        let mut counts = hand.counts();
+        let jokers = std::mem::take(&mut counts[Label::Jack as usize]);
+        let (largest, second) = two_largest(counts);
+        HandType::from_groups(largest + jokers, second)
+    }
+}
+
+fn two_largest(counts: [u8; 13]) -> (u8, u8) {
+    counts.into_iter().fold((0, 0), |(largest, second), count| {
+        if count > largest {
+            (count, largest)
+        } else {
+            (largest, second.max(count))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn hand(input: &str) -> Hand {
+        Hand::parse(input).unwrap().1
+    }
+
+    #[rstest]
+    #[case("23456", HandType::HighCard)]
+    #[case("A23A4", HandType::OnePair)]
+    #[case("23432", HandType::TwoPair)]
+    #[case("TTT98", HandType::ThreeOfAKind)]
+    #[case("23332", HandType::FullHouse)]
+    #[case("AA8AA", HandType::FourOfAKind)]
+    #[case("AAAAA", HandType::FiveOfAKind)]
+    #[case("KTJJT", HandType::TwoPair)]
+    #[case("JJJJJ", HandType::FiveOfAKind)]
+    fn test_standard_hand_type(#[case] input: &str, #[case] expected: HandType) {
+        assert_eq!(expected, Standard::hand_type(&hand(input)));
+    }
+
+    #[rstest]
+    #[case("23456", HandType::HighCard)]
+    #[case("2345J", HandType::OnePair)]
+    #[case("23432", HandType::TwoPair)]
+    #[case("2344J", HandType::ThreeOfAKind)]
+    #[case("234JJ", HandType::ThreeOfAKind)]
+    #[case("2233J", HandType::FullHouse)]
+    #[case("2333J", HandType::FourOfAKind)]
+    #[case("KTJJT", HandType::FourOfAKind)]
+    #[case("23JJJ", HandType::FourOfAKind)]
+    #[case("2222J", HandType::FiveOfAKind)]
+    #[case("22JJJ", HandType::FiveOfAKind)]
+    #[case("2JJJJ", HandType::FiveOfAKind)]
+    #[case("JJJJJ", HandType::FiveOfAKind)]
+    fn test_jokers_wild_hand_type(#[case] input: &str, #[case] expected: HandType) {
+        assert_eq!(expected, JokersWild::hand_type(&hand(input)));
+    }
+
+    #[rstest]
+    #[case("2AAAA", "33332")]
+    #[case("77788", "77888")]
+    #[case("KTJJT", "KK677")]
+    #[case("QQQJA", "KKKJA")]
+    #[case("2345J", "2345Q")]
+    fn test_standard_key(#[case] weaker: &str, #[case] stronger: &str) {
+        assert!(Standard::key(&hand(stronger)) > Standard::key(&hand(weaker)));
+    }
+
+    #[rstest]
+    #[case("JKKK2", "QQQQ2")]
+    #[case("T55J5", "QQQJA")]
+    #[case("QQQJA", "KTJJT")]
+    #[case("JJJJJ", "22222")]
+    #[case("2345Q", "2345J")]
+    fn test_jokers_wild_key(#[case] weaker: &str, #[case] stronger: &str) {
+        assert!(JokersWild::key(&hand(stronger)) > JokersWild::key(&hand(weaker)));
+    }
+
+    #[test]
+    fn test_strength() {
+        let standard: Vec<u8> = Label::ALL.into_iter().map(Standard::strength).collect();
+        assert_eq!((0..13).collect::<Vec<u8>>(), standard);
+
+        let mut jokers_wild: Vec<u8> = Label::ALL.into_iter().map(JokersWild::strength).collect();
+        jokers_wild.sort_unstable();
+        assert_eq!((0..13).collect::<Vec<u8>>(), jokers_wild);
+        assert_eq!(0, JokersWild::strength(Label::Jack));
+    }
+}
@@ -0,0 +1,27 @@
+//! Run with `cargo test -p day-7 --features dhat-heap --test alloc_budget`.
+#![cfg(feature = "dhat-heap")]
+
+use common::alloc_budget::{check, Budget};
+use day_7::*;
+
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+const INPUT: &str = include_str!("../input.txt");
+
+const PART1: Budget<u64> = Budget {
+    answer: 250120186,
+    total_blocks: 1010,
+    max_bytes: 20288,
+};
+const PART2: Budget<u64> = Budget {
+    answer: 250665248,
+    total_blocks: 1010,
+    max_bytes: 20288,
+};
+
+#[test]
+fn process_stays_within_budget() {
+    check("part1", INPUT, PART1, part1::process);
+    check("part2", INPUT, PART2, part2::process);
+}
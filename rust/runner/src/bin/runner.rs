@@ -0,0 +1,123 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Instant,
+};
+
+use clap::{Parser, Subcommand};
+use runner::{zig, SOLUTIONS};
+
+/// Runs the solutions of every day.
+#[derive(Debug, Parser)]
+struct Args {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Runs the registered solutions on their `day-N/input.txt` and prints the answers with
+    /// their timings.
+    Run {
+        /// Only this day.
+        day: Option<u8>,
+        /// Only this part of the day.
+        part: Option<u8>,
+        /// Input file to use instead of the day's `input.txt`.
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        /// Also run the solutions that take more than a minute.
+        #[arg(long)]
+        include_slow: bool,
+    },
+    /// Builds and runs the Zig solutions and compares their answers with the Rust ones
+    /// on the same inputs. Does nothing if `zig` is not installed.
+    Zig {
+        /// The Zig project.
+        #[arg(long, default_value = zig::DEFAULT_DIR)]
+        dir: PathBuf,
+        /// Also run the Rust solutions that take more than a minute.
+        #[arg(long)]
+        include_slow: bool,
+    },
+}
+
+fn main() -> ExitCode {
+    let result = match Args::parse().command {
+        Command::Run {
+            day,
+            part,
+            input,
+            include_slow,
+        } => run(day, part, input.as_deref(), include_slow),
+        Command::Zig { dir, include_slow } => compare_with_zig(&dir, include_slow),
+    };
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<&Path>,
+    include_slow: bool,
+) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let solutions: Vec<_> = SOLUTIONS
+        .iter()
+        .filter(|s| day.is_none_or(|day| s.day == day) && part.is_none_or(|part| s.part == part))
+        .collect();
+    if solutions.is_empty() {
+        return Err("no such solution".into());
+    }
+
+    for solution in solutions {
+        if solution.slow && !include_slow && part.is_none() {
+            println!(
+                "Day {} Part {}: skipped, pass --include-slow to run it",
+                solution.day, solution.part
+            );
+            continue;
+        }
+        let path = input.map_or_else(
+            || {
+                Path::new(env!("CARGO_MANIFEST_DIR"))
+                    .join(format!("../day-{}/input.txt", solution.day))
+            },
+            Path::to_path_buf,
+        );
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {e}", path.display()))?;
+        let start = Instant::now();
+        let answer = (solution.solve)(&input);
+        let time = start.elapsed();
+        println!(
+            "Day {} Part {}: {answer}\t(Time: {time:.2?})",
+            solution.day, solution.part
+        );
+    }
+    Ok(ExitCode::SUCCESS)
+}
+
+fn compare_with_zig(
+    dir: &Path,
+    include_slow: bool,
+) -> Result<ExitCode, Box<dyn std::error::Error>> {
+    let Some(zig) = zig::find() else {
+        eprintln!("zig is not installed, skipping the comparison");
+        return Ok(ExitCode::SUCCESS);
+    };
+    let output = zig::run(&zig, dir)?;
+    let report = zig::compare(zig::parse_output(&output), dir, include_slow)?;
+    print!("{report}");
+    Ok(if report.mismatches().next().is_some() {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    })
+}
@@ -0,0 +1,84 @@
+pub mod zig;
+
+/// A solution of one part of a day's puzzle.
+#[derive(Debug, Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    pub solve: fn(&str) -> String,
+    /// Takes more than a minute on the puzzle input, even in release mode.
+    pub slow: bool,
+}
+
+macro_rules! solutions {
+    ($($day:literal => $krate:ident $(, slow part $slow:literal)?;)*) => {
+        &[$(
+            Solution {
+                day: $day,
+                part: 1,
+                solve: |input| $krate::part1::process(input).to_string(),
+                slow: false $(|| $slow == 1)?,
+            },
+            Solution {
+                day: $day,
+                part: 2,
+                solve: |input| $krate::part2::process(input).to_string(),
+                slow: false $(|| $slow == 2)?,
+            },
+        )*]
+    };
+}
+
+/// Every solution, ordered by day and part.
+pub const SOLUTIONS: &[Solution] = solutions! {
+    1 => day_1;
+    2 => day_2;
+    3 => day_3;
+    4 => day_4;
+    5 => day_5, slow part 2;
+    6 => day_6;
+    7 => day_7;
+};
+
+pub fn find(day: u8, part: u8) -> Option<&'static Solution> {
+    SOLUTIONS
+        .iter()
+        .find(|solution| solution.day == day && solution.part == part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 1, "1abc2\npqr3stu8vwx", "50")]
+    #[case(1, 2, "two1nine\neightwothree", "112")]
+    #[case(4, 1, "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53", "8")]
+    #[case(6, 2, "Time:      7  15   30\nDistance:  9  40  200", "71503")]
+    #[case(7, 1, "32T3K 765\nT55J5 684", "2133")]
+    fn test_find(#[case] day: u8, #[case] part: u8, #[case] input: &str, #[case] expected: &str) {
+        let solution = find(day, part).unwrap();
+        assert_eq!((day, part), (solution.day, solution.part));
+        assert_eq!(expected, (solution.solve)(input));
+    }
+
+    #[test]
+    fn test_solutions() {
+        let keys: Vec<(u8, u8)> = SOLUTIONS.iter().map(|s| (s.day, s.part)).collect();
+        let mut sorted = keys.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(sorted, keys);
+        assert!(find(26, 1).is_none());
+        assert_eq!(
+            vec![(5, 2)],
+            SOLUTIONS
+                .iter()
+                .filter(|s| s.slow)
+                .map(|s| (s.day, s.part))
+                .collect::<Vec<_>>()
+        );
+    }
+}
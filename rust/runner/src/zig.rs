@@ -0,0 +1,272 @@
+//! Differential check of the Rust solutions against the Zig ones in `zig/`.
+
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    process::{Command, ExitStatus},
+    time::{Duration, Instant},
+};
+
+use crate::SOLUTIONS;
+
+/// The Zig project of this repository.
+pub const DEFAULT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../zig");
+
+#[derive(Debug, thiserror::Error)]
+pub enum ZigError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error("`zig build run` failed with {status}:\n{stderr}")]
+    Build { status: ExitStatus, stderr: String },
+}
+
+/// The `zig` executable from the `ZIG` environment variable or the `PATH`,
+/// if it can report its version.
+pub fn find() -> Option<PathBuf> {
+    let zig = env::var_os("ZIG").map_or_else(|| PathBuf::from("zig"), PathBuf::from);
+    let version = Command::new(&zig).arg("version").output().ok()?;
+    version.status.success().then_some(zig)
+}
+
+/// Builds and runs the Zig project in `dir` with optimizations, returning what it printed.
+pub fn run(zig: &Path, dir: &Path) -> Result<String, ZigError> {
+    let output = Command::new(zig)
+        .args(["build", "run", "-Doptimize=ReleaseFast"])
+        .current_dir(dir)
+        .output()?;
+    if !output.status.success() {
+        return Err(ZigError::Build {
+            status: output.status,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// An answer printed by the Zig `main` as `Day N Part M: value\t(Time: ...)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answer {
+    pub day: u8,
+    pub part: u8,
+    pub value: String,
+    /// `None` when the part was skipped and its answer hardcoded.
+    pub time: Option<Duration>,
+}
+
+/// The answers among the lines of the Zig output; other lines are ignored.
+pub fn parse_output(output: &str) -> Vec<Answer> {
+    output.lines().filter_map(parse_line).collect()
+}
+
+fn parse_line(line: &str) -> Option<Answer> {
+    let rest = line.strip_prefix("Day ")?;
+    let (day, rest) = rest.split_once(" Part ")?;
+    let (part, rest) = rest.split_once(": ")?;
+    let (value, timing) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let time = timing
+        .trim()
+        .strip_prefix("(Time: ")
+        .and_then(|time| time.strip_suffix(')'))
+        .and_then(parse_time);
+    Some(Answer {
+        day: day.parse().ok()?,
+        part: part.parse().ok()?,
+        value: value.to_owned(),
+        time,
+    })
+}
+
+/// Parses the durations printed by `printNanos`: `123ns`, `1.5μs`, `2.25ms` or `3s`.
+fn parse_time(time: &str) -> Option<Duration> {
+    let units = [("ns", 1.0), ("μs", 1e3), ("ms", 1e6), ("s", 1e9)];
+    let (amount, nanos) = units
+        .into_iter()
+        .find_map(|(unit, nanos)| Some((time.strip_suffix(unit)?, nanos)))?;
+    let nanos = (amount.parse::<f64>().ok()? * nanos).round();
+    (nanos.is_finite() && nanos >= 0.0).then(|| Duration::from_nanos(nanos as u64))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Match,
+    Mismatch {
+        rust: String,
+    },
+    /// There is no Rust solution for the part.
+    Missing,
+    /// The Rust solution is too slow to be run.
+    Skipped,
+}
+
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub zig: Answer,
+    pub outcome: Outcome,
+    pub rust_time: Option<Duration>,
+}
+
+/// Runs the Rust solution of every Zig answer on the input the Zig solution embeds,
+/// `src/dayN/input.txt` in the Zig project `dir`.
+pub fn compare(answers: Vec<Answer>, dir: &Path, include_slow: bool) -> io::Result<Report> {
+    let mut comparisons = Vec::with_capacity(answers.len());
+    for zig in answers {
+        let solution = SOLUTIONS
+            .iter()
+            .find(|solution| (solution.day, solution.part) == (zig.day, zig.part));
+        let (outcome, rust_time) = match solution {
+            None => (Outcome::Missing, None),
+            Some(solution) if solution.slow && !include_slow => (Outcome::Skipped, None),
+            Some(solution) => {
+                let input_path = dir.join(format!("src/day{}/input.txt", zig.day));
+                let input = fs::read_to_string(input_path)?;
+                let start = Instant::now();
+                let rust = (solution.solve)(&input);
+                let time = start.elapsed();
+                let outcome = if rust == zig.value {
+                    Outcome::Match
+                } else {
+                    Outcome::Mismatch { rust }
+                };
+                (outcome, Some(time))
+            }
+        };
+        comparisons.push(Comparison {
+            zig,
+            outcome,
+            rust_time,
+        });
+    }
+    Ok(Report(comparisons))
+}
+
+#[derive(Debug, Clone)]
+pub struct Report(pub Vec<Comparison>);
+
+impl Report {
+    pub fn mismatches(&self) -> impl Iterator<Item = &Comparison> {
+        self.0
+            .iter()
+            .filter(|comparison| matches!(comparison.outcome, Outcome::Mismatch { .. }))
+    }
+}
+
+/// One line per part with both answers and the Zig time relative to the Rust one.
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for Comparison {
+            zig,
+            outcome,
+            rust_time,
+        } in &self.0
+        {
+            write!(f, "Day {} Part {}: ", zig.day, zig.part)?;
+            match outcome {
+                Outcome::Match => write!(f, "ok       {}", zig.value)?,
+                Outcome::Mismatch { rust } => {
+                    write!(f, "MISMATCH zig {} != rust {rust}", zig.value)?
+                }
+                Outcome::Missing => write!(f, "missing  no Rust solution")?,
+                Outcome::Skipped => write!(f, "skipped  slow Rust solution")?,
+            }
+            match (zig.time, rust_time) {
+                (Some(zig), Some(rust)) => write!(
+                    f,
+                    "\t(zig {zig:.2?}, rust {rust:.2?}, zig/rust {:.2})",
+                    zig.as_secs_f64() / rust.as_secs_f64()
+                )?,
+                (None, Some(rust)) => write!(f, "\t(rust {rust:.2?})")?,
+                (Some(zig), None) => write!(f, "\t(zig {zig:.2?})")?,
+                (None, None) => {}
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[test]
+    fn test_parse_output() {
+        let output = "Day 4 Part 1: 24848\t(Time: 512.3μs)
+Day 4 Part 2: 7258152\t(Time: 1.5ms)
+Day 5 Part 2: 23738616\t(Skip. Time: >1m)
+warning: something else
+";
+        assert_eq!(
+            vec![
+                Answer {
+                    day: 4,
+                    part: 1,
+                    value: "24848".to_owned(),
+                    time: Some(Duration::from_nanos(512_300)),
+                },
+                Answer {
+                    day: 4,
+                    part: 2,
+                    value: "7258152".to_owned(),
+                    time: Some(Duration::from_micros(1500)),
+                },
+                Answer {
+                    day: 5,
+                    part: 2,
+                    value: "23738616".to_owned(),
+                    time: None,
+                },
+            ],
+            parse_output(output)
+        );
+    }
+
+    #[rstest]
+    #[case("999ns", Some(Duration::from_nanos(999)))]
+    #[case("1.5μs", Some(Duration::from_nanos(1500)))]
+    #[case("20ms", Some(Duration::from_millis(20)))]
+    #[case("2s", Some(Duration::from_secs(2)))]
+    #[case(">1m", None)]
+    fn test_parse_time(#[case] time: &str, #[case] expected: Option<Duration>) {
+        assert_eq!(expected, parse_time(time));
+    }
+
+    #[test]
+    fn test_compare() {
+        let answer = |day, part, value: &str| Answer {
+            day,
+            part,
+            value: value.to_owned(),
+            time: Some(Duration::from_millis(1)),
+        };
+        let dir = Path::new(DEFAULT_DIR);
+        let report = compare(
+            vec![
+                answer(4, 1, "24848"),
+                answer(4, 2, "0"),
+                answer(5, 2, "23738616"),
+                answer(26, 1, "0"),
+            ],
+            dir,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                Outcome::Match,
+                Outcome::Mismatch {
+                    rust: "7258152".to_owned()
+                },
+                Outcome::Skipped,
+                Outcome::Missing,
+            ],
+            report
+                .0
+                .iter()
+                .map(|comparison| comparison.outcome.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1, report.mismatches().count());
+    }
+}
@@ -0,0 +1,22 @@
+use std::path::Path;
+
+use runner::zig;
+
+/// Compares every answer of the Zig solutions with the Rust one, skipped without `zig`.
+#[test]
+fn zig_answers_match_rust() {
+    let Some(zig) = zig::find() else {
+        eprintln!("zig is not installed, skipping");
+        return;
+    };
+    let dir = Path::new(zig::DEFAULT_DIR);
+    let output = zig::run(&zig, dir).unwrap();
+    let answers = zig::parse_output(&output);
+    assert!(
+        !answers.is_empty(),
+        "no answers in the zig output:\n{output}"
+    );
+
+    let report = zig::compare(answers, dir, false).unwrap();
+    assert_eq!(0, report.mismatches().count(), "\n{report}");
+}
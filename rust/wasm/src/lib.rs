@@ -0,0 +1,94 @@
+//! The solutions of every day for JavaScript, built with `cargo xtask wasm`.
+
+use runner::Solution;
+use wasm_bindgen::prelude::*;
+
+/// Solves the part of the day's puzzle on the input.
+///
+/// Fails if there is no such solution. Invalid inputs make the solutions panic,
+/// which aborts the module in the `wasm-release` profile, so the message of the
+/// panic is logged to the console before that.
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    let solution =
+        runner::find(day, part).ok_or_else(|| format!("no solution for day {day} part {part}"))?;
+    log_panics();
+    run(solution, input)
+}
+
+/// Whether the solution takes more than a minute on the puzzle input.
+#[wasm_bindgen]
+pub fn slow(day: u8, part: u8) -> Option<bool> {
+    runner::find(day, part).map(|solution| solution.slow)
+}
+
+#[cfg(panic = "unwind")]
+fn run(solution: &Solution, input: &str) -> Result<String, String> {
+    std::panic::catch_unwind(|| (solution.solve)(input)).map_err(|payload| {
+        payload
+            .downcast_ref::<&str>()
+            .map(|message| (*message).to_owned())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "the solution panicked".to_owned())
+    })
+}
+
+#[cfg(not(panic = "unwind"))]
+fn run(solution: &Solution, input: &str) -> Result<String, String> {
+    Ok((solution.solve)(input))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn log_panics() {
+    #[wasm_bindgen]
+    extern "C" {
+        #[wasm_bindgen(js_namespace = console)]
+        fn error(message: &str);
+    }
+
+    static HOOK: std::sync::Once = std::sync::Once::new();
+    HOOK.call_once(|| std::panic::set_hook(Box::new(|info| error(&info.to_string()))));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn log_panics() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(1, 2, "two1nine\neightwothree", Ok("112"))]
+    #[case(3, 1, "467..114..\n...*......", Ok("467"))]
+    #[case(7, 2, "32T3K 765\nKTJJT 220", Ok("1205"))]
+    #[case(0, 1, "", Err("no solution for day 0 part 1"))]
+    #[case(1, 3, "", Err("no solution for day 1 part 3"))]
+    fn test_solve(
+        #[case] day: u8,
+        #[case] part: u8,
+        #[case] input: &str,
+        #[case] expected: Result<&str, &str>,
+    ) {
+        let actual = solve(day, part, input);
+        assert_eq!(
+            expected,
+            actual.as_deref().map_err(String::as_str),
+            "{actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_solve_invalid_input() {
+        let error = solve(6, 1, "Time: 7").unwrap_err();
+        assert!(error.starts_with("Invalid input"), "{error}");
+    }
+
+    #[test]
+    fn test_slow() {
+        assert_eq!(Some(true), slow(5, 2));
+        assert_eq!(Some(false), slow(5, 1));
+        assert_eq!(None, slow(26, 1));
+    }
+}
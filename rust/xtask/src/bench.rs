@@ -0,0 +1,366 @@
+//! History of the divan benchmark results, kept as JSON so that runs can be compared.
+
+use std::{
+    fmt::{self, Write},
+    fs, io,
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// The result of one divan benchmark, with times in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    /// The crate, like `day-2`.
+    pub day: String,
+    /// Path of the benchmark inside the crate, like `part1` or `group::part1`.
+    pub bench: String,
+    pub fastest: f64,
+    pub slowest: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub samples: u64,
+    pub iters: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Run {
+    pub commit: String,
+    /// Whether the working tree had uncommitted changes.
+    pub dirty: bool,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub measurements: Vec<Measurement>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Loads the history, which is empty if the file does not exist yet.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json).map_err(io::Error::from),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Compares the latest run with the most recent earlier run of the `baseline` commit,
+    /// or with the most recent earlier measurement of every benchmark without a `baseline`.
+    pub fn compare(&self, baseline: Option<&str>, threshold: f64) -> Option<Comparison> {
+        let (latest, earlier) = self.runs.split_last()?;
+        let find = |measurement: &Measurement| {
+            earlier
+                .iter()
+                .rev()
+                .filter(|run| baseline.is_none_or(|commit| run.commit.starts_with(commit)))
+                .find_map(|run| {
+                    run.measurements
+                        .iter()
+                        .find(|m| (&m.day, &m.bench) == (&measurement.day, &measurement.bench))
+                })
+        };
+        let rows = latest
+            .measurements
+            .iter()
+            .map(|measurement| Row {
+                day: measurement.day.clone(),
+                bench: measurement.bench.clone(),
+                baseline: find(measurement).map(|m| m.median),
+                latest: measurement.median,
+            })
+            .collect();
+        Some(Comparison { rows, threshold })
+    }
+}
+
+/// Parses the table divan prints for the benchmarks of the `day` crate.
+pub fn parse_divan(day: &str, output: &str) -> Vec<Measurement> {
+    let mut groups: Vec<String> = Vec::new();
+    let mut measurements = Vec::new();
+    // the first line holds the name of the benchmark target and the column titles
+    for line in output
+        .lines()
+        .skip_while(|line| !line.contains("fastest"))
+        .skip(1)
+    {
+        let name_start = line
+            .char_indices()
+            .find(|&(_, ch)| !matches!(ch, '│' | '├' | '╰' | '─' | ' '))
+            .map_or(line.len(), |(i, _)| i);
+        let depth = line[..name_start].chars().count() / 3;
+        let mut columns = line[name_start..].split('│').map(str::trim);
+        let Some(first) = columns.next().filter(|first| !first.is_empty()) else {
+            continue;
+        };
+        let (name, fastest) = first
+            .split_once(char::is_whitespace)
+            .map_or((first, ""), |(name, fastest)| (name, fastest.trim()));
+
+        groups.truncate(depth.saturating_sub(1));
+        if fastest.is_empty() {
+            groups.push(name.to_owned());
+            continue;
+        }
+        let mut bench = groups.join("::");
+        if !bench.is_empty() {
+            bench.push_str("::");
+        }
+        bench.push_str(name);
+
+        let times = (
+            parse_time(fastest),
+            columns.next().and_then(parse_time),
+            columns.next().and_then(parse_time),
+            columns.next().and_then(parse_time),
+        );
+        let counts = (
+            columns.next().and_then(|n| n.parse().ok()),
+            columns.next().and_then(|n| n.parse().ok()),
+        );
+        if let (
+            (Some(fastest), Some(slowest), Some(median), Some(mean)),
+            (Some(samples), Some(iters)),
+        ) = (times, counts)
+        {
+            measurements.push(Measurement {
+                day: day.to_owned(),
+                bench,
+                fastest,
+                slowest,
+                median,
+                mean,
+                samples,
+                iters,
+            });
+        }
+    }
+    measurements
+}
+
+/// Parses a divan duration such as `511.5 ns` or `1.2 ms` into nanoseconds.
+fn parse_time(time: &str) -> Option<f64> {
+    let (amount, unit) = time.split_once(' ')?;
+    let scale = match unit {
+        "ps" => 1e-3,
+        "ns" => 1.0,
+        "µs" | "μs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+    Some(amount.parse::<f64>().ok()? * scale)
+}
+
+/// Formats nanoseconds the way divan does.
+fn format_time(nanos: f64) -> String {
+    let (amount, unit) = [(1e9, "s"), (1e6, "ms"), (1e3, "µs")]
+        .into_iter()
+        .find(|&(scale, _)| nanos >= scale)
+        .map_or((nanos, "ns"), |(scale, unit)| (nanos / scale, unit));
+    let amount = format!("{amount:.3}");
+    format!(
+        "{} {unit}",
+        amount.trim_end_matches('0').trim_end_matches('.')
+    )
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    pub day: String,
+    pub bench: String,
+    /// Median of the baseline, if it has the benchmark.
+    pub baseline: Option<f64>,
+    /// Median of the latest run.
+    pub latest: f64,
+}
+
+impl Row {
+    /// Relative change of the median from the baseline.
+    pub fn change(&self) -> Option<f64> {
+        self.baseline
+            .map(|baseline| (self.latest - baseline) / baseline)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub rows: Vec<Row>,
+    /// Relative slowdown above which a benchmark is a regression.
+    pub threshold: f64,
+}
+
+impl Comparison {
+    pub fn regressions(&self) -> impl Iterator<Item = &Row> {
+        self.rows
+            .iter()
+            .filter(|row| row.change().is_some_and(|change| change > self.threshold))
+    }
+}
+
+/// A markdown table of the medians of every benchmark.
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "| Day | Benchmark | Baseline | Latest | Change |")?;
+        writeln!(f, "|---|---|--:|--:|--:|")?;
+        for row in &self.rows {
+            let mut change = String::new();
+            match row.change() {
+                Some(relative) => {
+                    write!(change, "{:+.1}%", relative * 100.0)?;
+                    if relative > self.threshold {
+                        change.push_str(" **regression**");
+                    }
+                }
+                None => change.push_str("new"),
+            }
+            writeln!(
+                f,
+                "| {} | {} | {} | {} | {change} |",
+                row.day,
+                row.bench,
+                row.baseline.map_or_else(|| "-".to_owned(), format_time),
+                format_time(row.latest),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const OUTPUT: &str = "\
+day_2               fastest       │ slowest       │ median        │ mean          │ samples │ iters
+├─ part1            73.83 µs      │ 135.9 µs      │ 90.33 µs      │ 91.6 µs       │ 100     │ 100
+├─ group                          │               │               │               │         │
+│  ├─ bytes         16.06 µs      │ 85.56 µs      │ 18.16 µs      │ 19.19 µs      │ 100     │ 100
+│  ╰─ generated     75.47 ms      │ 135.1 ms      │ 120 ms        │ 115.8 ms      │ 100     │ 100
+╰─ part2            511.5 ns      │ 6.665 µs      │ 544.5 ns      │ 614.8 ns      │ 100     │ 200
+";
+
+    fn measurement(day: &str, bench: &str, median: f64) -> Measurement {
+        Measurement {
+            day: day.to_owned(),
+            bench: bench.to_owned(),
+            fastest: median,
+            slowest: median,
+            median,
+            mean: median,
+            samples: 100,
+            iters: 100,
+        }
+    }
+
+    fn run(commit: &str, measurements: Vec<Measurement>) -> Run {
+        Run {
+            commit: commit.to_owned(),
+            dirty: false,
+            timestamp: 0,
+            measurements,
+        }
+    }
+
+    #[test]
+    fn test_parse_divan() {
+        let measurements = parse_divan("day-2", OUTPUT);
+        assert_eq!(
+            vec!["part1", "group::bytes", "group::generated", "part2"],
+            measurements
+                .iter()
+                .map(|m| m.bench.as_str())
+                .collect::<Vec<_>>()
+        );
+        let part2 = &measurements[3];
+        assert_eq!(
+            (511.5, 6665.0, 544.5, 614.8, 100, 200),
+            (
+                part2.fastest,
+                part2.slowest,
+                part2.median,
+                part2.mean,
+                part2.samples,
+                part2.iters
+            )
+        );
+        assert_eq!(120e6, measurements[2].median);
+    }
+
+    #[rstest]
+    #[case(544.5, "544.5 ns")]
+    #[case(90_330.0, "90.33 µs")]
+    #[case(120e6, "120 ms")]
+    #[case(2.5e9, "2.5 s")]
+    fn test_format_time(#[case] nanos: f64, #[case] expected: &str) {
+        assert_eq!(expected, format_time(nanos));
+    }
+
+    #[test]
+    fn test_compare() {
+        let history = History {
+            runs: vec![
+                run(
+                    "aaaaaaa",
+                    vec![
+                        measurement("day-1", "part1", 100.0),
+                        measurement("day-1", "part2", 100.0),
+                    ],
+                ),
+                run("bbbbbbb", vec![measurement("day-1", "part1", 200.0)]),
+                run(
+                    "ccccccc",
+                    vec![
+                        measurement("day-1", "part1", 210.0),
+                        measurement("day-1", "part2", 50.0),
+                        measurement("day-2", "part1", 10.0),
+                    ],
+                ),
+            ],
+        };
+
+        let latest = history.compare(None, 0.1).unwrap();
+        assert_eq!(
+            vec![Some(200.0), Some(100.0), None],
+            latest
+                .rows
+                .iter()
+                .map(|row| row.baseline)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(0, latest.regressions().count());
+
+        let first = history.compare(Some("aaa"), 0.1).unwrap();
+        assert_eq!(
+            vec![("day-1", "part1")],
+            first
+                .regressions()
+                .map(|row| (row.day.as_str(), row.bench.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "\
+| Day | Benchmark | Baseline | Latest | Change |
+|---|---|--:|--:|--:|
+| day-1 | part1 | 100 ns | 210 ns | +110.0% **regression** |
+| day-1 | part2 | 100 ns | 50 ns | -50.0% |
+| day-2 | part1 | - | 10 ns | new |
+",
+            first.to_string()
+        );
+
+        assert_eq!(None, History::default().compare(None, 0.1));
+    }
+}
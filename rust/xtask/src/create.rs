@@ -0,0 +1,387 @@
+//! Scaffolding of a new day from `daily-template`.
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+const TEMPLATE: &str = "daily-template";
+const RUNNER_MANIFEST: &str = "runner/Cargo.toml";
+const RUNNER_LIB: &str = "runner/src/lib.rs";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// File with the example input of the puzzle, `-` for the standard input.
+    pub example: Option<PathBuf>,
+    /// File with a different example input for part 2.
+    pub example2: Option<PathBuf>,
+    /// Expected answers on the examples.
+    pub answers: [Option<String>; 2],
+    /// Fetch the puzzle input through the cache.
+    pub fetch: bool,
+}
+
+/// Creates the crate of the `day` (`day-N`), registers it in the runner
+/// and fills its input and examples.
+pub fn create(root: &Path, day: &str, options: &Options) -> io::Result<()> {
+    let number = day_number(day).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{day:?} is not named `day-N`"),
+        )
+    })?;
+    let dir = root.join(day);
+    if dir.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", dir.display()),
+        ));
+    }
+
+    // reading everything up front, so that nothing is created if a file is missing
+    let example = options.example.as_deref().map(read_example).transpose()?;
+    let example2 = options.example2.as_deref().map(read_example).transpose()?;
+    let input = if options.fetch {
+        Some(fetch_input(number)?)
+    } else {
+        None
+    };
+    let manifest = fs::read_to_string(root.join(RUNNER_MANIFEST))?;
+    let lib = fs::read_to_string(root.join(RUNNER_LIB))?;
+    let (manifest, lib) = register(&manifest, &lib, day, number)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("{RUNNER_LIB}: {e}")))?;
+
+    let variables = variables(day, options);
+    copy_template(&root.join(TEMPLATE), &dir, &variables)?;
+    fs::write(dir.join("example.txt"), example.unwrap_or_default())?;
+    if let Some(example2) = example2 {
+        fs::write(dir.join("example2.txt"), example2)?;
+    }
+    if let Some(input) = input {
+        fs::write(dir.join("input.txt"), input)?;
+    }
+    fs::write(root.join(RUNNER_MANIFEST), manifest)?;
+    fs::write(root.join(RUNNER_LIB), lib)?;
+    Ok(())
+}
+
+fn day_number(day: &str) -> Option<u8> {
+    day.strip_prefix("day-")?
+        .parse()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+}
+
+fn read_example(path: &Path) -> io::Result<String> {
+    if path == Path::new("-") {
+        let mut example = String::new();
+        io::stdin().read_to_string(&mut example)?;
+        Ok(example)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// The values of the `{{name}}` placeholders of the template.
+fn variables(day: &str, options: &Options) -> Vec<(&'static str, String)> {
+    let mut variables = vec![
+        ("project-name", day.to_owned()),
+        ("crate_name", day.replace('-', "_")),
+        (
+            "part2-example",
+            if options.example2.is_some() {
+                "example2.txt"
+            } else {
+                "example.txt"
+            }
+            .to_owned(),
+        ),
+    ];
+    for (part, answer) in options.answers.iter().enumerate() {
+        let (ignore, answer) = match answer {
+            Some(answer) => (String::new(), answer.clone()),
+            None => (
+                "\n    #[ignore = \"no expected answer for the example yet\"]".to_owned(),
+                "0".to_owned(),
+            ),
+        };
+        let (ignore_name, answer_name) = [
+            ("part1-ignore", "part1-answer"),
+            ("part2-ignore", "part2-answer"),
+        ][part];
+        variables.push((ignore_name, ignore));
+        variables.push((answer_name, answer));
+    }
+    variables
+}
+
+fn render(template: &str, variables: &[(&str, String)]) -> String {
+    variables
+        .iter()
+        .fold(template.to_owned(), |text, (name, value)| {
+            text.replace(&format!("{{{{{name}}}}}"), value)
+        })
+}
+
+fn copy_template(from: &Path, to: &Path, variables: &[(&str, String)]) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let (from, to) = (entry.path(), to.join(entry.file_name()));
+        if entry.file_type()?.is_dir() {
+            copy_template(&from, &to, variables)?;
+        } else {
+            fs::write(to, render(&fs::read_to_string(from)?, variables))?;
+        }
+    }
+    Ok(())
+}
+
+/// Adds the day to the dependencies of the runner and to its `SOLUTIONS`.
+fn register(manifest: &str, lib: &str, day: &str, number: u8) -> Result<(String, String), String> {
+    let dependency = format!("{day} = {{ path = \"../{day}\" }}\n");
+    let manifest = insert_after_last(manifest, "day-", &dependency)
+        .ok_or("no day among the runner dependencies")?;
+
+    let solutions = lib
+        .find("pub const SOLUTIONS")
+        .ok_or("no `SOLUTIONS` constant")?;
+    let end = solutions
+        + lib[solutions..]
+            .find("\n};")
+            .ok_or("unterminated `SOLUTIONS`")?;
+    let mut lib = lib.to_owned();
+    lib.insert_str(
+        end + 1,
+        &format!("    {number} => {};\n", day.replace('-', "_")),
+    );
+    Ok((manifest, lib))
+}
+
+/// Inserts the `line` after the last line starting with the `prefix`.
+fn insert_after_last(text: &str, prefix: &str, line: &str) -> Option<String> {
+    let mut offset = 0;
+    let mut after = None;
+    for existing in text.split_inclusive('\n') {
+        offset += existing.len();
+        if existing.starts_with(prefix) {
+            after = Some(offset);
+        }
+    }
+    let mut text = text.to_owned();
+    text.insert_str(after?, line);
+    Some(text)
+}
+
+/// The input of the day from the cache, downloaded with the `AOC_SESSION` cookie on a miss.
+///
+/// The cache is `AOC_CACHE_DIR`, or `advent-of-code-2023` in the user's cache directory.
+fn fetch_input(day: u8) -> io::Result<String> {
+    let cache = cache_dir()?.join(format!("day-{day}.txt"));
+    if let Ok(input) = fs::read_to_string(&cache) {
+        return Ok(input);
+    }
+
+    let session = env::var("AOC_SESSION").map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "set AOC_SESSION to the session cookie of adventofcode.com to fetch the input",
+        )
+    })?;
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--cookie"])
+        .arg(format!("session={session}"))
+        .args(["--user-agent", "advent-of-code-2023 xtask"])
+        .arg(format!("https://adventofcode.com/2023/day/{day}/input"))
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "failed to fetch the input: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let input = String::from_utf8(output.stdout)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    if let Some(dir) = cache.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&cache, &input)?;
+    Ok(input)
+}
+
+fn cache_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = env::var_os("AOC_CACHE_DIR") {
+        return Ok(dir.into());
+    }
+    let base = env::var_os("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("LOCALAPPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "no cache directory, set AOC_CACHE_DIR",
+            )
+        })?;
+    Ok(base.join("advent-of-code-2023"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    #[rstest]
+    #[case("day-8", Some(8))]
+    #[case("day-25", Some(25))]
+    #[case("day-26", None)]
+    #[case("day-0", None)]
+    #[case("day8", None)]
+    #[case("day-x", None)]
+    fn test_day_number(#[case] day: &str, #[case] expected: Option<u8>) {
+        assert_eq!(expected, day_number(day));
+    }
+
+    #[test]
+    fn test_render() {
+        let template = "use {{crate_name}}::part1;
+    #[test]{{part1-ignore}}
+    fn test_process() {
+        assert_eq!({{part1-answer}}, process(include_str!(\"../{{part2-example}}\")));
+    }";
+        let answered = Options {
+            example2: Some("example2.txt".into()),
+            answers: [Some("142".to_owned()), None],
+            ..Options::default()
+        };
+        assert_eq!(
+            "use day_8::part1;
+    #[test]
+    fn test_process() {
+        assert_eq!(142, process(include_str!(\"../example2.txt\")));
+    }",
+            render(template, &variables("day-8", &answered))
+        );
+        assert_eq!(
+            "use day_8::part1;
+    #[test]
+    #[ignore = \"no expected answer for the example yet\"]
+    fn test_process() {
+        assert_eq!(0, process(include_str!(\"../example.txt\")));
+    }",
+            render(template, &variables("day-8", &Options::default()))
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        let manifest = "[dependencies]
+day-1 = { path = \"../day-1\" }
+day-7 = { path = \"../day-7\" }
+clap.workspace = true
+";
+        let lib = "pub const SOLUTIONS: &[Solution] = solutions! {
+    1 => day_1;
+    7 => day_7;
+};
+
+pub fn find() {}
+";
+        let (manifest, lib) = register(manifest, lib, "day-8", 8).unwrap();
+        assert_eq!(
+            "[dependencies]
+day-1 = { path = \"../day-1\" }
+day-7 = { path = \"../day-7\" }
+day-8 = { path = \"../day-8\" }
+clap.workspace = true
+",
+            manifest
+        );
+        assert_eq!(
+            "pub const SOLUTIONS: &[Solution] = solutions! {
+    1 => day_1;
+    7 => day_7;
+    8 => day_8;
+};
+
+pub fn find() {}
+",
+            lib
+        );
+        assert!(register("[dependencies]\n", &lib, "day-9", 9).is_err());
+    }
+
+    /// A copy of the parts of the workspace `create` reads and writes, in the temporary directory.
+    fn workspace(name: &str) -> PathBuf {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        let root = env::temp_dir().join(format!("xtask-{name}-{}", std::process::id()));
+        if root.exists() {
+            fs::remove_dir_all(&root).unwrap();
+        }
+        copy_template(&workspace.join(TEMPLATE), &root.join(TEMPLATE), &[]).unwrap();
+        fs::create_dir_all(root.join("runner/src")).unwrap();
+        for file in [RUNNER_MANIFEST, RUNNER_LIB] {
+            fs::copy(workspace.join(file), root.join(file)).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_create() {
+        let root = workspace("create");
+        fs::write(root.join("example.txt"), "1abc2\n").unwrap();
+        let options = Options {
+            example: Some(root.join("example.txt")),
+            answers: [Some("12".to_owned()), None],
+            ..Options::default()
+        };
+        create(&root, "day-9", &options).unwrap();
+
+        let day = root.join("day-9");
+        let read = |path: &str| fs::read_to_string(day.join(path)).unwrap();
+        assert!(read("Cargo.toml").contains("name = \"day-9\""));
+        assert_eq!("1abc2\n", read("example.txt"));
+        assert!(!day.join("example2.txt").exists());
+        assert!(read("src/part1.rs").contains("#[test]\n    fn test_process() {"));
+        assert!(read("src/part1.rs").contains("assert_eq!(12, process(input));"));
+        assert!(
+            read("src/part2.rs").contains("#[ignore = \"no expected answer for the example yet\"]")
+        );
+        for file in [
+            "Cargo.toml",
+            "src/lib.rs",
+            "src/bin/part1.rs",
+            "benches/benchmarks.rs",
+        ] {
+            assert!(!read(file).contains("{{"), "{file} has a placeholder left");
+        }
+        let manifest = fs::read_to_string(root.join(RUNNER_MANIFEST)).unwrap();
+        assert!(manifest.contains("day-9 = { path = \"../day-9\" }\n"));
+        let lib = fs::read_to_string(root.join(RUNNER_LIB)).unwrap();
+        assert!(lib.contains("    9 => day_9;\n};"));
+
+        let error = create(&root, "day-9", &options).unwrap_err();
+        assert_eq!(io::ErrorKind::AlreadyExists, error.kind());
+        assert_eq!(
+            manifest,
+            fs::read_to_string(root.join(RUNNER_MANIFEST)).unwrap()
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_create_missing_example() {
+        let root = workspace("create-missing-example");
+        let options = Options {
+            example: Some(root.join("missing.txt")),
+            ..Options::default()
+        };
+        let error = create(&root, "day-9", &options).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+        assert!(!root.join("day-9").exists());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,542 @@
+//! Development tasks of the workspace, run with `cargo xtask <task>`.
+
+use std::{
+    ffi::OsString,
+    fs::{self, OpenOptions},
+    io,
+    path::{Path, PathBuf},
+    process::{Command, ExitCode, Stdio},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use clap::{Parser, Subcommand};
+
+mod bench;
+mod create;
+
+/// Where `bench-record` keeps the results of every run.
+const HISTORY: &str = "benchmarks/history.json";
+const WASM_TARGET: &str = "wasm32-unknown-unknown";
+
+#[derive(Debug, Parser)]
+#[command(bin_name = "cargo xtask")]
+struct Args {
+    #[command(subcommand)]
+    task: Task,
+}
+
+#[derive(Debug, Clone, PartialEq, Subcommand)]
+enum Task {
+    /// Runs the binary of the day in release mode.
+    Run { day: String, part: String },
+    /// Runs the binary of the day in debug mode.
+    RunDev { day: String, part: String },
+    /// Lints the day with clippy.
+    Lint { day: String },
+    /// Runs the tests of the day matching the part, with nextest if it is installed.
+    Test { day: String, part: String },
+    /// Benchmarks the whole workspace into `benchmarks/benchmarks.txt`.
+    BenchAll,
+    /// Benchmarks the part of the day, appending to `benchmarks/<day>.bench.txt`.
+    Bench { day: String, part: String },
+    /// Benchmarks the days, all of them by default, and appends their results to the history.
+    BenchRecord {
+        days: Vec<String>,
+        /// Also benchmarks the parts that take minutes on the puzzle input.
+        #[arg(long)]
+        slow: bool,
+        #[arg(long, default_value = HISTORY)]
+        history: PathBuf,
+    },
+    /// Compares the latest recorded benchmarks with a baseline as a markdown table,
+    /// failing if any of them regressed.
+    BenchCompare {
+        /// Commit (or its prefix) of the baseline run, the previous measurement of every
+        /// benchmark by default.
+        #[arg(long)]
+        baseline: Option<String>,
+        /// Slowdown of the median, in percent, above which a benchmark regressed.
+        #[arg(long, default_value_t = 5.0)]
+        threshold: f64,
+        #[arg(long, default_value = HISTORY)]
+        history: PathBuf,
+    },
+    /// Profiles the binary of the day into `flamegraphs/<day>_<part>.svg`.
+    Flamegraph { day: String, part: String },
+    /// Runs the binary of the day with the dhat heap profiler.
+    Dhat { day: String, part: String },
+    /// Checks the heap usage of the day's solutions against their budgets in
+    /// `<day>/tests/alloc_budget.rs`.
+    AllocBudget { day: String },
+    /// Builds the `wasm` crate with the `wasm-release` profile and generates its JavaScript
+    /// bindings into `target/wasm/<bindings>` with `wasm-bindgen`.
+    Wasm {
+        /// The `--target` of `wasm-bindgen`.
+        #[arg(long, default_value = "web")]
+        bindings: String,
+        /// Also checks the module on the examples of every day with node.
+        #[arg(long)]
+        test: bool,
+    },
+    /// Creates a new day from `daily-template` and registers it in the runner.
+    Create {
+        /// Name of the crate, `day-N`.
+        day: String,
+        /// File with the example input of the puzzle, `-` to paste it into the standard input.
+        #[arg(long)]
+        example: Option<PathBuf>,
+        /// File with the example input of part 2, if it differs from the one of part 1.
+        #[arg(long)]
+        example2: Option<PathBuf>,
+        /// Answer of part 1 on the example.
+        #[arg(long)]
+        part1: Option<String>,
+        /// Answer of part 2 on the example.
+        #[arg(long)]
+        part2: Option<String>,
+        /// Fetch the puzzle input, from the cache or with the `AOC_SESSION` cookie.
+        #[arg(long)]
+        fetch: bool,
+    },
+}
+
+/// Where the standard output of a step goes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Output {
+    Inherit,
+    Create(PathBuf),
+    Append(PathBuf),
+}
+
+/// A `cargo` invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Step {
+    args: Vec<OsString>,
+    output: Output,
+}
+
+impl Step {
+    fn cargo<I: IntoIterator<Item = S>, S: Into<OsString>>(args: I) -> Self {
+        Self {
+            args: args.into_iter().map(Into::into).collect(),
+            output: Output::Inherit,
+        }
+    }
+
+    fn output(self, output: Output) -> Self {
+        Self { output, ..self }
+    }
+
+    /// Runs the step in the workspace `root`.
+    fn run(&self, root: &Path) -> io::Result<bool> {
+        let stdout = match &self.output {
+            Output::Inherit => Stdio::inherit(),
+            Output::Create(path) | Output::Append(path) => {
+                let path = root.join(path);
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir)?;
+                }
+                let append = matches!(self.output, Output::Append(_));
+                OpenOptions::new()
+                    .create(true)
+                    .write(true)
+                    .append(append)
+                    .truncate(!append)
+                    .open(path)?
+                    .into()
+            }
+        };
+        let status = Command::new(cargo())
+            .args(&self.args)
+            .current_dir(root)
+            .stdout(stdout)
+            .status()?;
+        Ok(status.success())
+    }
+}
+
+impl Task {
+    /// The `cargo` invocation of the task, if it is a single one.
+    fn step(&self, nextest: bool) -> Option<Step> {
+        let step = match self {
+            Self::Run { day, part } => Step::cargo(["run", "-p", day, "--bin", part, "-r"]),
+            Self::RunDev { day, part } => Step::cargo(["run", "-p", day, "--bin", part]),
+            Self::Lint { day } => Step::cargo(["clippy", "-p", day]),
+            Self::Test { day, part } if nextest => Step::cargo(["nextest", "run", "-p", day, part]),
+            Self::Test { day, part } => Step::cargo(["test", "-p", day, part]),
+            Self::BenchAll => Step::cargo(["bench", "-q"])
+                .output(Output::Create("benchmarks/benchmarks.txt".into())),
+            Self::Bench { day, part } => Step::cargo(["bench", "--bench", day, part])
+                .output(Output::Append(format!("benchmarks/{day}.bench.txt").into())),
+            Self::Flamegraph { day, part } => {
+                let svg = format!("flamegraphs/{day}_{part}.svg");
+                Step::cargo([
+                    "flamegraph",
+                    "--profile",
+                    "flamegraph",
+                    "--root",
+                    "--package",
+                    day,
+                    "--bin",
+                    part,
+                    "-o",
+                    &svg,
+                ])
+            }
+            Self::Dhat { day, part } => Step::cargo([
+                "run",
+                "--profile",
+                "dhat",
+                "--features",
+                "dhat-heap",
+                "--package",
+                day,
+                "--bin",
+                part,
+            ]),
+            Self::AllocBudget { day } => Step::cargo([
+                "test",
+                "--features",
+                "dhat-heap",
+                "--package",
+                day,
+                "--test",
+                "alloc_budget",
+            ]),
+            Self::Wasm { .. } => Step::cargo([
+                "build",
+                "--package",
+                "wasm",
+                "--target",
+                WASM_TARGET,
+                "--profile",
+                "wasm-release",
+            ]),
+            Self::BenchRecord { .. } | Self::BenchCompare { .. } | Self::Create { .. } => {
+                return None
+            }
+        };
+        Some(step)
+    }
+}
+
+/// The workspace this crate belongs to.
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask is inside the workspace")
+        .to_path_buf()
+}
+
+/// The `cargo` running this task, if any.
+fn cargo() -> OsString {
+    std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into())
+}
+
+fn has_nextest() -> bool {
+    Command::new(cargo())
+        .args(["nextest", "--version"])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Output of `git` in the workspace `root`.
+fn git(root: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git").args(args).current_dir(root).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("git {} failed", args.join(" "))));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned())
+}
+
+/// The crates with benchmarks, named after their bench targets.
+fn benchmarked_days(root: &Path) -> io::Result<Vec<String>> {
+    let mut days = Vec::new();
+    for entry in fs::read_dir(root)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with("day-") && entry.path().join("benches").is_dir() {
+            days.push(name);
+        }
+    }
+    days.sort_by_key(|day| (day.len(), day.clone()));
+    Ok(days)
+}
+
+/// Parts of the days whose benchmarks take minutes, skipped by `bench-record` unless asked.
+const SLOW_BENCHES: &[(&str, &str)] = &[("day-5", "part2")];
+
+/// Arguments of `cargo bench` for the day.
+fn bench_args(day: &str, slow: bool) -> Vec<String> {
+    let mut args = ["bench", "-q", "--bench", day, "--", "--exact"]
+        .map(str::to_owned)
+        .to_vec();
+    if !slow {
+        for (_, part) in SLOW_BENCHES.iter().filter(|(slow_day, _)| *slow_day == day) {
+            // divan matches the whole path of the benchmark, which starts with the crate name.
+            args.extend([
+                "--skip".to_owned(),
+                format!("{}::{part}", day.replace('-', "_")),
+            ]);
+        }
+    }
+    args
+}
+
+fn bench_record(root: &Path, days: Vec<String>, slow: bool, history: &Path) -> io::Result<bool> {
+    let days = if days.is_empty() {
+        benchmarked_days(root)?
+    } else {
+        days
+    };
+    let mut measurements = Vec::new();
+    for day in &days {
+        let output = Command::new(cargo())
+            .args(bench_args(day, slow))
+            .current_dir(root)
+            .stderr(Stdio::inherit())
+            .output()?;
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        print!("{stdout}");
+        if !output.status.success() {
+            return Ok(false);
+        }
+        measurements.extend(bench::parse_divan(day, &stdout));
+    }
+
+    let history = root.join(history);
+    let mut runs = bench::History::load(&history)?;
+    runs.runs.push(bench::Run {
+        commit: git(root, &["rev-parse", "--short", "HEAD"])?,
+        dirty: !git(root, &["status", "--porcelain"])?.is_empty(),
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_secs()),
+        measurements,
+    });
+    runs.save(&history)?;
+    println!("recorded the run in {}", history.display());
+    Ok(true)
+}
+
+fn bench_compare(
+    root: &Path,
+    baseline: Option<&str>,
+    threshold: f64,
+    history: &Path,
+) -> io::Result<bool> {
+    let history = bench::History::load(&root.join(history))?;
+    let Some(comparison) = history.compare(baseline, threshold / 100.0) else {
+        eprintln!("error: no benchmarks recorded, run `cargo xtask bench-record` first");
+        return Ok(false);
+    };
+    print!("{comparison}");
+    let regressed = comparison.regressions().next().is_some();
+    Ok(!regressed)
+}
+
+/// Generates the `bindings` of the module built by the `wasm` task, then runs
+/// `wasm/tests/examples.mjs` on the node ones if `test`.
+fn wasm(root: &Path, bindings: &str, test: bool) -> io::Result<bool> {
+    let module = root.join(format!("target/{WASM_TARGET}/wasm-release/wasm.wasm"));
+    let wasm_bindgen = |bindings: &str| {
+        Command::new("wasm-bindgen")
+            .args(["--target", bindings, "--out-dir"])
+            .arg(root.join("target/wasm").join(bindings))
+            .arg(&module)
+            .status()
+            .map(|status| status.success())
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => io::Error::other(
+                    "wasm-bindgen is not installed, install the version of the `wasm-bindgen` \
+                     crate in Cargo.lock with `cargo install wasm-bindgen-cli --version <version>`",
+                ),
+                _ => e,
+            })
+    };
+    if !wasm_bindgen(bindings)? {
+        return Ok(false);
+    }
+    println!(
+        "generated {}",
+        root.join("target/wasm").join(bindings).display()
+    );
+    if !test {
+        return Ok(true);
+    }
+    if bindings != "nodejs" && !wasm_bindgen("nodejs")? {
+        return Ok(false);
+    }
+    let status = Command::new("node")
+        .arg(root.join("wasm/tests/examples.mjs"))
+        .arg(root.join("target/wasm/nodejs/wasm.js"))
+        .status()?;
+    Ok(status.success())
+}
+
+fn main() -> ExitCode {
+    let task = Args::parse().task;
+    let root = root();
+    let result = match &task {
+        Task::BenchRecord {
+            days,
+            slow,
+            history,
+        } => Some(bench_record(&root, days.clone(), *slow, history)),
+        Task::BenchCompare {
+            baseline,
+            threshold,
+            history,
+        } => Some(bench_compare(
+            &root,
+            baseline.as_deref(),
+            *threshold,
+            history,
+        )),
+        Task::Create {
+            day,
+            example,
+            example2,
+            part1,
+            part2,
+            fetch,
+        } => {
+            let options = create::Options {
+                example: example.clone(),
+                example2: example2.clone(),
+                answers: [part1.clone(), part2.clone()],
+                fetch: *fetch,
+            };
+            Some(create::create(&root, day, &options).map(|()| {
+                println!("created {day}, run `cargo xtask test {day} part1`");
+                true
+            }))
+        }
+        Task::Wasm { bindings, test } => task.step(false).map(|build| {
+            build
+                .run(&root)
+                .and_then(|built| Ok(built && wasm(&root, bindings, *test)?))
+        }),
+        _ => None,
+    };
+    if let Some(result) = result {
+        return match result {
+            Ok(true) => ExitCode::SUCCESS,
+            Ok(false) => ExitCode::FAILURE,
+            Err(e) => {
+                eprintln!("error: {e}");
+                ExitCode::FAILURE
+            }
+        };
+    }
+    if let Task::Flamegraph { .. } = task {
+        if let Err(e) = fs::create_dir_all(root.join("flamegraphs")) {
+            eprintln!("error: failed to create flamegraphs/: {e}");
+            return ExitCode::FAILURE;
+        }
+    }
+    let nextest = matches!(task, Task::Test { .. }) && has_nextest();
+    let Some(step) = task.step(nextest) else {
+        unreachable!("{task:?} is handled above");
+    };
+    match step.run(&root) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: failed to run cargo: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    fn args(step: &Step) -> String {
+        step.args
+            .iter()
+            .map(|arg| arg.to_str().unwrap())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[rstest]
+    #[case(&["run", "day-1", "part1"], "run -p day-1 --bin part1 -r", Output::Inherit)]
+    #[case(&["run-dev", "day-1", "part2"], "run -p day-1 --bin part2", Output::Inherit)]
+    #[case(&["lint", "day-3"], "clippy -p day-3", Output::Inherit)]
+    #[case(&["test", "day-2", "part1"], "test -p day-2 part1", Output::Inherit)]
+    #[case(
+        &["bench-all"],
+        "bench -q",
+        Output::Create("benchmarks/benchmarks.txt".into())
+    )]
+    #[case(
+        &["bench", "day-4", "part2"],
+        "bench --bench day-4 part2",
+        Output::Append("benchmarks/day-4.bench.txt".into())
+    )]
+    #[case(
+        &["flamegraph", "day-5", "part1"],
+        "flamegraph --profile flamegraph --root --package day-5 --bin part1 -o flamegraphs/day-5_part1.svg",
+        Output::Inherit
+    )]
+    #[case(
+        &["dhat", "day-6", "part2"],
+        "run --profile dhat --features dhat-heap --package day-6 --bin part2",
+        Output::Inherit
+    )]
+    #[case(
+        &["wasm", "--test"],
+        "build --package wasm --target wasm32-unknown-unknown --profile wasm-release",
+        Output::Inherit
+    )]
+    #[case(
+        &["alloc-budget", "day-7"],
+        "test --features dhat-heap --package day-7 --test alloc_budget",
+        Output::Inherit
+    )]
+    fn test_step(#[case] task: &[&str], #[case] expected: &str, #[case] output: Output) {
+        let Args { task } = Args::try_parse_from(["xtask"].iter().chain(task)).unwrap();
+        let step = task.step(false).unwrap();
+        assert_eq!(expected, args(&step));
+        assert_eq!(output, step.output);
+    }
+
+    #[test]
+    fn test_step_nextest() {
+        let task = Task::Test {
+            day: "day-2".to_owned(),
+            part: "part1".to_owned(),
+        };
+        assert_eq!(
+            "nextest run -p day-2 part1",
+            args(&task.step(true).unwrap())
+        );
+    }
+
+    #[rstest]
+    #[case("day-4", false, "bench -q --bench day-4 -- --exact")]
+    #[case(
+        "day-5",
+        false,
+        "bench -q --bench day-5 -- --exact --skip day_5::part2"
+    )]
+    #[case("day-5", true, "bench -q --bench day-5 -- --exact")]
+    fn test_bench_args(#[case] day: &str, #[case] slow: bool, #[case] expected: &str) {
+        assert_eq!(expected, bench_args(day, slow).join(" "));
+    }
+
+    #[rstest]
+    #[case(&["bench-record"])]
+    #[case(&["bench-compare"])]
+    #[case(&["create", "day-8"])]
+    fn test_step_several_commands(#[case] task: &[&str]) {
+        let Args { task } = Args::try_parse_from(["xtask"].iter().chain(task)).unwrap();
+        assert!(task.step(false).is_none(), "{task:?}");
+    }
+}